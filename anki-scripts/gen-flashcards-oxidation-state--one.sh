@@ -32,19 +32,19 @@ svg_raw=$(
     --mark "$color______same_common: z != $atomic_number && oxidation_states.common == $common
                                                          && oxidation_states.notable != $notable" \
     --mark "$color_common_in_common: z != $atomic_number && $common != {}
-                                                         && $common in oxidation_states.common
+                                                         && $common subset oxidation_states.common
                                                          && $common != oxidation_states.common" \
     --mark "$color_______all_in_all: z != $atomic_number && ($common + $notable) != {}
-                                                         && ($common + $notable) in (oxidation_states.common + oxidation_states.notable)
+                                                         && ($common + $notable) subset (oxidation_states.common + oxidation_states.notable)
                                                          && !(oxidation_states.common == $common
                                                                 && oxidation_states.notable == $notable)
                                                          && !($notable == {}
-                                                                && $common in oxidation_states.common)" \
+                                                                && $common subset oxidation_states.common)" \
     --mark "$color____common_in_all: z != $atomic_number && $common != {}
-                                                         && $common in (oxidation_states.common + oxidation_states.notable)
-                                                         && !($common in oxidation_states.common)
+                                                         && $common subset (oxidation_states.common + oxidation_states.notable)
+                                                         && !($common subset oxidation_states.common)
                                                          && oxidation_states.common != $common
-                                                         && !(($common + $notable) in (oxidation_states.common + oxidation_states.notable))"
+                                                         && !(($common + $notable) subset (oxidation_states.common + oxidation_states.notable))"
 )
 
 svg=$(
@@ -1,4 +1,4 @@
-use clap::{ArgAction, Parser};
+use clap::{ArgAction, Parser, ValueEnum};
 use regex::Regex;
 use std::borrow::Cow;
 
@@ -10,6 +10,19 @@ pub struct Args {
     #[arg(long)]
     pub no_symbols: bool,
 
+    /// Show standard atomic weights under the symbols
+    #[arg(long)]
+    pub mass: bool,
+
+    /// Show element names under the symbols
+    #[arg(long)]
+    pub names: bool,
+
+    /// Language of element names in tooltips and --names, and of ‘local_name’ in --dump and
+    /// queries. ‘name’ stays in English
+    #[arg(long, value_enum, default_value_t = crate::l10n::Lang::En)]
+    pub lang: crate::l10n::Lang,
+
     /// Hide atomic numbers
     #[arg(long)]
     pub no_z: bool,
@@ -26,6 +39,33 @@ pub struct Args {
     #[arg(long)]
     pub wide: bool,
 
+    /// Style element symbols by their phase at STP, or at --temperature: outlined for gases,
+    /// italic for liquids
+    #[arg(long)]
+    pub phase_style: bool,
+
+    /// Temperature in K at which to compute ‘phase_at_t’ for queries and --phase-style
+    #[arg(
+        long,
+        value_name = "KELVIN",
+        value_parser = parse_temperature,
+        allow_negative_numbers = true
+    )]
+    pub temperature: Option<f64>,
+
+    /// Only show elements discovered until this YEAR (negative for BCE), see --undiscovered
+    #[arg(long, value_name = "YEAR", allow_negative_numbers = true)]
+    pub as_of: Option<i16>,
+
+    /// How to draw elements not yet discovered --as-of the given year
+    #[arg(long, value_enum, requires = "as_of", default_value_t = Undiscovered::Grey)]
+    pub undiscovered: Undiscovered,
+
+    /// Also draw the hypothetical elements 119–168 of period 8 with their systematic names,
+    /// placed by the Madelung rule, i.e. with a g-block of 121–138
+    #[arg(long)]
+    pub extended: bool,
+
     /// Draw helium in group 2 instead of 18 (for electron configurations)
     #[arg(long)]
     pub helium_in_2: bool,
@@ -34,31 +74,153 @@ pub struct Args {
     ///
     /// Some examples:{n}
     ///   - 'pink: z == 1'{n}
+    ///   - 'pink: name == "Iron"'{n}
+    ///   - 'pink: symbol in {"Fe", "Co", "Ni"}'{n}
+    ///   - 'pink: name ~ "ium$" && symbol !~ "^C"'{n}
+    ///   - 'pink: category == "halogen"'{n}
+    ///   - 'pink: mass > 200'{n}
+    ///   - 'pink: electronegativity >= 2.55 && covalent_radius_pm / 100 < 0.75'{n}
+    ///   - 'pink: config.d == 5 && valence_electrons == 1'{n}
+    ///   - 'pink: ionization_energy.first_kj_per_mol > 1000'{n}
+    ///   - 'pink: phase == "liquid" || melting_point < 300'{n}
+    ///   - 'pink: discovered > 1869 && discovered < 1900'{n}
+    ///   - 'pink: radioactive || 40 in isotopes'{n}
     ///   - 'pink: z >= 11 && z < 19'{n}
+    ///   - 'pink: z in 11..19 || z in 37..=38'{n}
     ///   - 'cyan: group == 5 || (group == 15 && period <= 6)'{n}
     ///   - 'hsl(240, 100%, 80%): block == 0 || block == 1'{n}
     ///   - '#ccccff: 1 in oxidation_states.common'{n}
-    ///   - 'lime: {-1, 1} in oxidation_states.common'{n}
+    ///   - 'lime: {-1, 1} subset oxidation_states.common'{n}
+    ///   - 'lime: 4 not in oxidation_states.common && len(oxidation_states.common & {2, 3}) == 2'{n}
+    ///   - 'lime: (oxidation_states.common ^ {2, 3}) == {}'{n}
     ///   - 'lime: 0 in (oxidation_states.common + oxidation_states.notable)'{n}
     ///   - 'lime: 1 in (oxidation_states.predicted)'{n}
     ///   - 'lime: 1 in (oxidation_states.citation_needed)'{n}
-    ///   - 'wheat: (group - 10) in oxidation_states.common || group in oxidation_states.common'
+    ///   - 'wheat: (group - 10) in oxidation_states.common || group in oxidation_states.common'{n}
+    ///   - 'wheat: len(oxidation_states.common) > 4'{n}
+    ///   - 'wheat: max(oxidation_states.common) == group - 10'{n}
+    ///   - 'wheat: {x | x in oxidation_states.common, x / 2 * 2 != x} == {}'{n}
+    ///   - 'wheat: 0 in {x - group + 10 | x in oxidation_states.common}'
+    ///
+    /// Set operators: ‘in’ and ‘not in’ for membership, ‘subset’, ‘superset’ and
+    /// ‘proper_subset’, and ‘+’ (union), ‘-’ (difference), ‘&’ (intersection) and ‘^’
+    /// (symmetric difference). Ranges of integers, e.g. ‘11..19’ without 19 and ‘-4..=8’ with 8,
+    /// are sets, and so are comprehensions like ‘{x * 2 | x in SET}’ or, with a filter,
+    /// ‘{x | x in SET, x > 0}’.
+    ///
+    /// Functions: len(set or string), min(set), max(set), abs(number), sum(set).
     #[arg(long, value_name = "COLOR:QUERY_EXPR", value_parser = parse_mark_query, action = ArgAction::Append )]
     pub mark: Vec<MarkQuery>,
 
+    /// Color elements continuously by a numeric QUERY_EXPR, normalized across all drawn
+    /// elements. The SCALE is either ‘viridis’ (default), ‘grayscale’, ‘diverging’ (centered at
+    /// 0), or colors from the minimum to the maximum. Elements without data are hatched.
+    ///
+    /// Some examples:{n}
+    ///   - 'electronegativity:blue..red'{n}
+    ///   - 'mass:white..#ffa500..red'{n}
+    ///   - 'period * 2 + group'{n}
+    ///   - 'group - 10:diverging'
+    #[arg(long, value_name = "QUERY_EXPR[:SCALE]", value_parser = parse_gradient_query)]
+    pub gradient: Option<GradientQuery>,
+
+    /// Color elements by a predefined property, with a legend
+    #[arg(long, value_enum, conflicts_with = "gradient")]
+    pub color_by: Option<ColorBy>,
+
+    /// Overlay element data from a JSON or TOML FILE, can be provided multiple times.
+    ///
+    /// JSON files are arrays of records in the same format as --dump, TOML ones have an
+    /// [[element]] table per record. Each record needs an atomic_number, and only replaces the
+    /// fields it has. Unknown properties can be added under ‘extra’, and queried as
+    /// ‘extra.<key>’.
+    #[arg(long, value_name = "FILE", action = ArgAction::Append)]
+    pub data: Vec<std::path::PathBuf>,
+
+    /// Override oxidation states of an element, can be provided multiple times, and is applied
+    /// after --override-ox-file.
+    ///
+    /// Kinds are ‘common’, ‘notable’, ‘predicted’ and ‘citation_needed’. States added to one
+    /// kind are removed from the others.
+    ///
+    /// Some examples:{n}
+    ///   - 'Mn.common={2, 4, 7}'{n}
+    ///   - 'Mn.common+={4}'{n}
+    ///   - 'Fe.notable-={-4, -2}'
+    #[arg(long, value_name = "SYMBOL.KIND=STATES", value_parser = crate::overrides::OxidationOverride::parse, action = ArgAction::Append)]
+    pub override_ox: Vec<crate::overrides::OxidationOverride>,
+
+    /// Read --override-ox overrides from a FILE, one per line, with ‘#’ comments. Can be
+    /// provided multiple times. The overrides are recorded in the SVG description.
+    #[arg(long, value_name = "FILE", value_parser = crate::overrides::OxidationOverride::parse_file, action = ArgAction::Append)]
+    pub override_ox_file: Vec<Vec<crate::overrides::OxidationOverride>>,
+
+    /// Compare a locally saved copy of the wikitext of Wikipedia’s ‘Template:Infobox
+    /// element/symbol-to-oxidation-state’ with the built-in oxidation states. The differences
+    /// are printed in the format of --override-ox-file.
+    #[arg(long, value_name = "FILE")]
+    pub diff_ox_template: Option<std::path::PathBuf>,
+
     /// Dumps all know data about elements matching QUERY_EXPR as JSON.
     #[arg(long, value_name = "QUERY_EXPR", value_parser = crate::query::Query::new)]
     pub dump: Option<crate::query::Query>,
 
+    /// Instead of the table, draw an orbital box diagram of the element with this atomic number
+    #[arg(long, value_name = "ATOMIC_NUMBER")]
+    pub orbitals: Option<u8>,
+
+    /// Only draw the subshells outside of the noble gas core in --orbitals
+    #[arg(long, requires = "orbitals")]
+    pub valence_only: bool,
+
+    /// Draw an ion with this charge in --orbitals, e.g. 2 for Fe²⁺ or -1 for Cl⁻
+    #[arg(
+        long,
+        requires = "orbitals",
+        allow_negative_numbers = true,
+        default_value_t = 0
+    )]
+    pub charge: i8,
+
+    /// Instead of the table, draw a chart of nuclides of all isotopes in the data set. --mark
+    /// queries can then also use: n, a, half_life (in s, null if stable), abundance, decay_mode
+    /// (alpha, beta-, ec, 2beta-, 2ec, sf), and stable.
+    #[arg(long, conflicts_with_all = ["orbitals", "gradient", "color_by"])]
+    pub nuclides: bool,
+
     /// Don't maximally downsize the viewbox to the bounding box of the table
     #[arg(long)]
     pub pretty_padding: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Undiscovered {
+    /// Draw them faded
+    Grey,
+    /// Leave their places empty
+    Hide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorBy {
+    /// Chemical series, e.g. alkali metals or halogens
+    Category,
+}
+
 impl Args {
     pub fn parse() -> Self {
         Parser::parse()
     }
+
+    /// From files first, in the order they are applied.
+    pub fn oxidation_overrides(
+        &self,
+    ) -> impl Iterator<Item = &crate::overrides::OxidationOverride> {
+        self.override_ox_file
+            .iter()
+            .flatten()
+            .chain(self.override_ox.iter())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -79,6 +241,48 @@ fn parse_mark_query(arg: &str) -> Result<MarkQuery, String> {
     Ok(MarkQuery { color, query })
 }
 
+#[derive(Debug, Clone)]
+pub struct GradientQuery {
+    pub query: crate::query::Query,
+    pub scale: crate::color::ColorScale,
+}
+
+fn parse_temperature(arg: &str) -> Result<f64, String> {
+    match arg.parse::<f64>() {
+        Ok(kelvin) if kelvin >= 0.0 => Ok(kelvin),
+        _ => Err("expected a non-negative number of kelvins".to_string()),
+    }
+}
+
+fn parse_gradient_query(arg: &str) -> Result<GradientQuery, String> {
+    // The scale is optional, and a colon can also occur in a string literal of the query:
+    if let Some((query, scale)) = arg.rsplit_once(':') {
+        if let Ok(scale) = crate::color::ColorScale::parse(scale) {
+            let query = crate::query::Query::new(query)?;
+            return Ok(GradientQuery { query, scale });
+        }
+    }
+    let query = crate::query::Query::new(arg)?;
+    let scale = crate::color::ColorScale::parse("viridis")?;
+    Ok(GradientQuery { query, scale })
+}
+
+/// Used for SVG comments, since the contents of --override-ox-file aren’t in the argv, unlike
+/// --override-ox values.
+pub fn override_notes(args: &Args) -> String {
+    let overrides: Vec<String> = (args.override_ox_file.iter().flatten())
+        .map(|oxidation_override| format!("\n      {}", oxidation_override))
+        .collect();
+    if overrides.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n    Oxidation state overrides from --override-ox-file:{}",
+            overrides.concat()
+        )
+    }
+}
+
 /// Used for SVG comments (future reproducibility).
 pub fn escaped_argv() -> String {
     std::env::args()
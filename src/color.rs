@@ -0,0 +1,293 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// An sRGB color that can be interpolated, unlike the free-form CSS colors of `--mark`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Supports `#rgb`, `#rrggbb`, `rgb(r, g, b)`, `hsl(h, s%, l%)` and some common CSS names.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        static RE_HEX: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#"^#([0-9a-fA-F]{3}){1,2}$"#).unwrap());
+        static RE_RGB: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#"^rgb\(\s*(\d+)\s*,\s*(\d+)\s*,\s*(\d+)\s*\)$"#).unwrap());
+        static RE_HSL: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r#"^hsl\(\s*(\d+(?:\.\d+)?)\s*,\s*(\d+(?:\.\d+)?)%\s*,\s*(\d+(?:\.\d+)?)%\s*\)$"#,
+            )
+            .unwrap()
+        });
+
+        let input = input.trim();
+        let error = || format!("Unsupported color: ‘{}’", input);
+
+        if RE_HEX.is_match(input) {
+            let hex = &input[1..];
+            let digits: Vec<u8> = if hex.len() == 3 {
+                hex.chars()
+                    .map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16).unwrap())
+                    .collect()
+            } else {
+                (0..3)
+                    .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+                    .collect()
+            };
+            Ok(Self(digits[0], digits[1], digits[2]))
+        } else if let Some(cpt) = RE_RGB.captures(input) {
+            let channel = |i| str::parse::<u8>(&cpt[i]).map_err(|_| error());
+            Ok(Self(channel(1)?, channel(2)?, channel(3)?))
+        } else if let Some(cpt) = RE_HSL.captures(input) {
+            let number = |i| str::parse::<f64>(&cpt[i]).unwrap();
+            Ok(Self::from_hsl(
+                number(1),
+                number(2) / 100.0,
+                number(3) / 100.0,
+            ))
+        } else {
+            NAMED_COLORS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(input))
+                .map(|&(_, hex)| Self::parse(hex).unwrap())
+                .ok_or_else(error)
+        }
+    }
+
+    fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        let channel = |a: f64| ((a + m) * 255.0).round() as u8;
+        Self(channel(r), channel(g), channel(b))
+    }
+
+    /// Linear interpolation in sRGB, the same as SVG gradients do by default.
+    pub fn interpolate(stops: &[Rgb], t: f64) -> Rgb {
+        match stops {
+            [] => Rgb(0, 0, 0),
+            [only] => *only,
+            _ => {
+                let t = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+                let i = (t.floor() as usize).min(stops.len() - 2);
+                let (a, b, t) = (stops[i], stops[i + 1], t - i as f64);
+                let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+                Rgb(channel(a.0, b.0), channel(a.1, b.1), channel(a.2, b.2))
+            }
+        }
+    }
+
+    /// Relative luminance, to pick a readable text color.
+    pub fn is_dark(&self) -> bool {
+        let luminance = 0.2126 * self.0 as f64 + 0.7152 * self.1 as f64 + 0.0722 * self.2 as f64;
+        luminance < 0.45 * 255.0
+    }
+}
+
+impl std::fmt::Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Colors of a continuous coloring, from the minimum to the maximum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScale {
+    pub stops: Vec<Rgb>,
+    /// Centered at 0, if the values have both signs.
+    pub diverging: bool,
+}
+
+impl ColorScale {
+    /// Either a named scale (`viridis`, `grayscale`, `diverging`), or colors separated by `..`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let named = |stops: &[&str], diverging| Self {
+            stops: stops.iter().map(|s| Rgb::parse(s).unwrap()).collect(),
+            diverging,
+        };
+        match input {
+            // Sampled from matplotlib’s viridis:
+            "viridis" => Ok(named(
+                &[
+                    "#440154", "#482878", "#3e4989", "#31688e", "#26828e", "#1f9e89", "#35b779",
+                    "#6ece58", "#b5de2b", "#fde725",
+                ],
+                false,
+            )),
+            "grayscale" | "greyscale" => Ok(named(&["#ffffff", "#000000"], false)),
+            // ColorBrewer’s RdBu:
+            "diverging" => Ok(named(&["#2166ac", "#f7f7f7", "#b2182b"], true)),
+            _ => {
+                let stops = input
+                    .split("..")
+                    .map(Rgb::parse)
+                    .collect::<Result<Vec<_>, _>>()?;
+                if stops.len() < 2 {
+                    return Err(
+                        "at least two colors are needed, e.g. ‘blue..red’, or a scale name"
+                            .to_string(),
+                    );
+                }
+                Ok(Self {
+                    stops,
+                    diverging: false,
+                })
+            }
+        }
+    }
+
+    /// Maps `value` from `[min, max]` to a color.
+    pub fn color(&self, value: f64, min: f64, max: f64) -> Rgb {
+        let (min, max) = self.domain(min, max);
+        let t = if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        };
+        Rgb::interpolate(&self.stops, t)
+    }
+
+    /// The actually used range of values, symmetric for diverging scales.
+    pub fn domain(&self, min: f64, max: f64) -> (f64, f64) {
+        if self.diverging && min < 0.0 && max > 0.0 {
+            let extent = min.abs().max(max.abs());
+            (-extent, extent)
+        } else {
+            (min, max)
+        }
+    }
+}
+
+static NAMED_COLORS: &[(&str, &str)] = &[
+    ("black", "#000000"),
+    ("white", "#ffffff"),
+    ("gray", "#808080"),
+    ("grey", "#808080"),
+    ("silver", "#c0c0c0"),
+    ("lightgray", "#d3d3d3"),
+    ("lightgrey", "#d3d3d3"),
+    ("red", "#ff0000"),
+    ("darkred", "#8b0000"),
+    ("crimson", "#dc143c"),
+    ("pink", "#ffc0cb"),
+    ("hotpink", "#ff69b4"),
+    ("orange", "#ffa500"),
+    ("darkorange", "#ff8c00"),
+    ("gold", "#ffd700"),
+    ("yellow", "#ffff00"),
+    ("lightyellow", "#ffffe0"),
+    ("wheat", "#f5deb3"),
+    ("tan", "#d2b48c"),
+    ("brown", "#a52a2a"),
+    ("lime", "#00ff00"),
+    ("green", "#008000"),
+    ("darkgreen", "#006400"),
+    ("lightgreen", "#90ee90"),
+    ("olive", "#808000"),
+    ("teal", "#008080"),
+    ("cyan", "#00ffff"),
+    ("aqua", "#00ffff"),
+    ("lightcyan", "#e0ffff"),
+    ("turquoise", "#40e0d0"),
+    ("blue", "#0000ff"),
+    ("navy", "#000080"),
+    ("darkblue", "#00008b"),
+    ("lightblue", "#add8e6"),
+    ("skyblue", "#87ceeb"),
+    ("steelblue", "#4682b4"),
+    ("purple", "#800080"),
+    ("violet", "#ee82ee"),
+    ("magenta", "#ff00ff"),
+    ("fuchsia", "#ff00ff"),
+    ("lavender", "#e6e6fa"),
+    ("indigo", "#4b0082"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_parse() {
+        assert_eq!(Rgb::parse("#ffa500"), Ok(Rgb(255, 165, 0)));
+        assert_eq!(Rgb::parse(" #F80 "), Ok(Rgb(255, 136, 0)));
+        assert_eq!(Rgb::parse("rgb(1, 2,3)"), Ok(Rgb(1, 2, 3)));
+        assert_eq!(Rgb::parse("hsl(240, 100%, 80%)"), Ok(Rgb(153, 153, 255)));
+        assert_eq!(Rgb::parse("hsl(0, 0%, 50%)"), Ok(Rgb(128, 128, 128)));
+        assert_eq!(Rgb::parse("Orange"), Ok(Rgb(255, 165, 0)));
+        assert_eq!(Rgb::parse("#ffa500").unwrap().to_string(), "#ffa500");
+        for input in ["#ffa50", "rgb(256, 0, 0)", "hsl(0, 0, 0)", "orangey"] {
+            assert_eq!(
+                Rgb::parse(input),
+                Err(format!("Unsupported color: ‘{}’", input))
+            );
+        }
+    }
+
+    #[test]
+    fn test_rgb_interpolate() {
+        let stops = [Rgb(0, 0, 0), Rgb(255, 255, 255), Rgb(255, 0, 0)];
+        assert_eq!(Rgb::interpolate(&stops, 0.0), stops[0]);
+        assert_eq!(Rgb::interpolate(&stops, 0.5), stops[1]);
+        assert_eq!(Rgb::interpolate(&stops, 1.0), stops[2]);
+        assert_eq!(Rgb::interpolate(&stops, 0.25), Rgb(128, 128, 128));
+        assert_eq!(Rgb::interpolate(&stops, 0.75), Rgb(255, 128, 128));
+        // Clamped:
+        assert_eq!(Rgb::interpolate(&stops, -1.0), stops[0]);
+        assert_eq!(Rgb::interpolate(&stops, 2.0), stops[2]);
+        assert_eq!(Rgb::interpolate(&stops[..1], 0.3), stops[0]);
+        assert!(Rgb(0, 0, 139).is_dark());
+        assert!(!Rgb(255, 255, 0).is_dark());
+    }
+
+    #[test]
+    fn test_color_scale_parse() {
+        let scale = ColorScale::parse("white..#ffa500..red").unwrap();
+        assert_eq!(
+            scale.stops,
+            vec![Rgb(255, 255, 255), Rgb(255, 165, 0), Rgb(255, 0, 0)]
+        );
+        assert!(!scale.diverging);
+        assert_eq!(ColorScale::parse(" viridis ").unwrap().stops.len(), 10);
+        assert!(ColorScale::parse("diverging").unwrap().diverging);
+        assert_eq!(
+            ColorScale::parse("greyscale"),
+            ColorScale::parse("grayscale")
+        );
+        assert!(ColorScale::parse("red")
+            .unwrap_err()
+            .starts_with("at least two colors are needed"));
+        assert_eq!(
+            ColorScale::parse("red..reddish"),
+            Err("Unsupported color: ‘reddish’".to_string())
+        );
+    }
+
+    #[test]
+    fn test_color_scale_color() {
+        let scale = ColorScale::parse("black..white").unwrap();
+        assert_eq!(scale.color(2.0, 2.0, 4.0), Rgb(0, 0, 0));
+        assert_eq!(scale.color(4.0, 2.0, 4.0), Rgb(255, 255, 255));
+        assert_eq!(scale.color(3.0, 2.0, 4.0), Rgb(128, 128, 128));
+        // All values the same:
+        assert_eq!(scale.color(7.0, 7.0, 7.0), Rgb(128, 128, 128));
+
+        let diverging = ColorScale::parse("diverging").unwrap();
+        assert_eq!(diverging.domain(-1.0, 4.0), (-4.0, 4.0));
+        assert_eq!(diverging.domain(1.0, 4.0), (1.0, 4.0));
+        assert_eq!(diverging.color(0.0, -1.0, 4.0), Rgb(247, 247, 247));
+        assert_eq!(diverging.color(4.0, -1.0, 4.0), Rgb(178, 24, 43));
+        assert_eq!(diverging.color(0.0, 0.0, 0.0), Rgb(247, 247, 247));
+    }
+}
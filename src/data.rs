@@ -0,0 +1,175 @@
+use crate::elements::Element;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Overlays element records from a JSON file (an array, like `--dump` prints) or a TOML one (an
+/// array of `[[element]]` tables). Each record needs an `atomic_number`, and replaces only the
+/// fields it contains, recursively for nested objects.
+pub fn overlay(elements: &mut [Element], path: &Path) -> Result<(), String> {
+    let error = |err: String| format!("Error loading {}: {}", path.display(), err);
+
+    let text = std::fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+    let records = if path.extension().is_some_and(|ext| ext == "toml") {
+        let mut document: serde_json::Value =
+            toml::from_str(&text).map_err(|err| error(err.to_string()))?;
+        document
+            .get_mut("element")
+            .map(serde_json::Value::take)
+            .ok_or_else(|| error("expected [[element]] tables".to_string()))?
+    } else {
+        serde_json::from_str(&text).map_err(|err| error(err.to_string()))?
+    };
+    let records = records
+        .as_array()
+        .ok_or_else(|| error("expected an array of element records".to_string()))?;
+
+    for record in records.iter() {
+        let atomic_number = record
+            .get("atomic_number")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| error(format!("record without an atomic_number: {}", record)))?;
+        let element = elements
+            .iter_mut()
+            .find(|element| element.atomic_number as u64 == atomic_number)
+            .ok_or_else(|| error(format!("unknown atomic number: {}", atomic_number)))?;
+
+        let mut merged = serde_json::to_value(&*element).unwrap();
+        // Derived from `full`, so that it’s only validated when given explicitly:
+        if let Some(config) = merged
+            .get_mut("electron_configuration")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            config.remove("abbreviated");
+        }
+        // The precision of the replaced value doesn’t apply to a new one:
+        if record
+            .get("atomic_weight")
+            .is_some_and(|weight| weight.get("value").is_some() && weight.get("decimals").is_none())
+        {
+            if let Some(weight) = merged
+                .get_mut("atomic_weight")
+                .and_then(serde_json::Value::as_object_mut)
+            {
+                weight.remove("decimals");
+            }
+        }
+        merge(&mut merged, record);
+
+        *element = Element::deserialize(merged)
+            .map_err(|err| error(format!("atomic number {}: {}", atomic_number, err)))?;
+        if let Some(isotope) =
+            (element.isotopes.iter()).find(|isotope| (isotope.mass_number as u64) < atomic_number)
+        {
+            return Err(error(format!(
+                "atomic number {}: mass number {} is less than the atomic number",
+                atomic_number, isotope.mass_number
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Objects are merged key by key, everything else (including arrays) is replaced.
+fn merge(base: &mut serde_json::Value, overlay: &serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay.iter() {
+                match base.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlay_text(name: &str, text: &str) -> Result<Vec<Element>, String> {
+        let path = std::env::temp_dir().join(format!("periodic-table-generator-{}", name));
+        std::fs::write(&path, text).unwrap();
+        let mut elements = crate::elements::ALL.clone();
+        let result = overlay(&mut elements, &path);
+        std::fs::remove_file(&path).unwrap();
+        result.map(|_| elements)
+    }
+
+    #[test]
+    fn test_overlay_json_and_toml() {
+        let json = overlay_text(
+            "test.json",
+            r#"[{"atomic_number": 26, "atomic_weight": {"value": 55.8}, "extra": {"n": 1}}]"#,
+        )
+        .unwrap();
+        let toml = overlay_text(
+            "test.toml",
+            "[[element]]\natomic_number = 26\natomic_weight.value = 55.8\nextra.n = 1\n",
+        )
+        .unwrap();
+        for elements in [json, toml] {
+            let iron = &elements[25];
+            let weight = iron.atomic_weight.unwrap();
+            assert_eq!(weight.value, 55.8);
+            // Merged, not replaced:
+            assert_eq!(weight.interval, None);
+            assert!(!weight.no_stable_isotopes);
+            assert_eq!(iron.name, "Iron");
+            assert_eq!(iron.extra["n"], serde_json::json!(1));
+            let query = crate::query::Query::new("extra.n == 1 && extra.missing == null").unwrap();
+            assert_eq!(query.evaluate_on(iron), Ok(true));
+            assert_eq!(query.evaluate_on(&elements[0]), Ok(false));
+        }
+
+        let elements = overlay_text(
+            "decimals.json",
+            r#"[{"atomic_number": 1, "extra": {"lines": [656.3, 486.1]}}]"#,
+        )
+        .unwrap();
+        let query = crate::query::Query::new("len(extra.lines) == 2").unwrap();
+        assert_eq!(
+            query.evaluate_on(&elements[0]),
+            Err("Eval: decimal numbers cannot be put in sets, but got 656.3; use comparisons instead (extra.lines)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_overlay_errors() {
+        let error = |name, text| overlay_text(name, text).unwrap_err();
+        assert!(error(
+            "unknown.json",
+            r#"[{"atomic_number": 26, "colour": "grey"}]"#
+        )
+        .contains("atomic number 26: unknown field `colour`"));
+        assert!(
+            error("type.json", r#"[{"atomic_number": 26, "period": "four"}]"#)
+                .contains("atomic number 26: invalid type: string \"four\"")
+        );
+        assert!(error("no-number.json", r#"[{"name": "Iron"}]"#)
+            .contains("record without an atomic_number"));
+        assert!(error("unknown-number.json", r#"[{"atomic_number": 200}]"#)
+            .contains("unknown atomic number: 200"));
+        assert!(error("tables.toml", "[[elements]]\natomic_number = 26\n")
+            .contains("expected [[element]] tables"));
+        assert!(error(
+            "isotope.json",
+            r#"[{"atomic_number": 26, "isotopes": [{"mass_number": 10}]}]"#
+        )
+        .contains("atomic number 26: mass number 10 is less than the atomic number"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut base = serde_json::json!({"a": {"b": 1, "c": [1, 2]}, "d": 2});
+        merge(&mut base, &serde_json::json!({"a": {"c": [3]}, "e": null}));
+        assert_eq!(
+            base,
+            serde_json::json!({"a": {"b": 1, "c": [3]}, "d": 2, "e": null})
+        );
+    }
+}
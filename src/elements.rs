@@ -1,123 +1,897 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Serialize;
-use std::collections::{BTreeSet, HashMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Symbol(String);
 
-impl std::string::ToString for Symbol {
-    fn to_string(&self) -> String {
-        self.0.clone()
+impl From<&str> for Symbol {
+    fn from(symbol: &str) -> Self {
+        Self(symbol.to_string())
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl std::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Element {
     pub atomic_number: u8,
     pub symbol: Symbol,
+    /// English name, e.g. “Iron”, also with `--lang`, so that queries don’t depend on it.
+    pub name: String,
+    /// Name in the `--lang` language, e.g. “Żelazo”, shown in labels and tooltips.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_name: Option<String>,
     pub group: Option<u8>,
     pub period: u8,
-    /// 0=s, 1=p, 2=d, 4=3
+    /// 0=s, 1=p, 2=d, 3=f, 4=g
     pub block: u8,
+    pub category: Category,
+    /// `None` for hypothetical elements from `--extended`.
+    pub atomic_weight: Option<AtomicWeight>,
+    pub electron_configuration: ElectronConfiguration,
+    /// On the Pauling scale, `None` where unknown.
+    pub electronegativity: Option<f64>,
+    pub covalent_radius_pm: Option<f64>,
+    pub van_der_waals_radius_pm: Option<f64>,
+    pub ionization_energy: IonizationEnergies,
+    /// Positive when energy is released.
+    pub electron_affinity_kj_per_mol: Option<f64>,
+    /// At standard temperature and pressure.
+    pub phase: Phase,
+    /// At `--temperature`, the same as `phase` without it.
+    pub phase_at_t: Phase,
+    /// In K.
+    pub melting_point: Option<f64>,
+    /// In K.
+    pub boiling_point: Option<f64>,
+    /// `None` for hypothetical elements from `--extended`.
+    pub discovery: Option<Discovery>,
+    pub isotopes: Vec<Isotope>,
     pub oxidation_states: OxidationStates,
+    /// Properties from `--data` files that aren’t known here, queryable as `extra.<key>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// `None` where unknown.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IonizationEnergies {
+    pub first_kj_per_mol: Option<f64>,
+}
+
+/// Chemical series, as commonly colored in periodic tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    AlkaliMetal,
+    AlkalineEarthMetal,
+    Lanthanide,
+    Actinide,
+    TransitionMetal,
+    PostTransitionMetal,
+    Metalloid,
+    ReactiveNonmetal,
+    Halogen,
+    NobleGas,
+    /// Superheavy elements, whose chemistry hasn’t been established.
+    Unknown,
+}
+
+impl Category {
+    pub const ALL: [Self; 11] = [
+        Self::AlkaliMetal,
+        Self::AlkalineEarthMetal,
+        Self::Lanthanide,
+        Self::Actinide,
+        Self::TransitionMetal,
+        Self::PostTransitionMetal,
+        Self::Metalloid,
+        Self::ReactiveNonmetal,
+        Self::Halogen,
+        Self::NobleGas,
+        Self::Unknown,
+    ];
+
+    /// Group 12 is counted among transition metals, and La and Ac among lanthanides and actinides.
+    fn of(atomic_number: u8, group: Option<u8>) -> Self {
+        match (atomic_number, group) {
+            (1 | 6 | 7 | 8 | 15 | 16 | 34, _) => Self::ReactiveNonmetal,
+            (5 | 14 | 32 | 33 | 51 | 52, _) => Self::Metalloid,
+            (9 | 17 | 35 | 53 | 85, _) => Self::Halogen,
+            (2 | 10 | 18 | 36 | 54 | 86, _) => Self::NobleGas,
+            (57..=71, _) => Self::Lanthanide,
+            (89..=103, _) => Self::Actinide,
+            (109.., _) => Self::Unknown, // Including `--extended`.
+            (_, Some(1)) => Self::AlkaliMetal,
+            (_, Some(2)) => Self::AlkalineEarthMetal,
+            (_, Some(3..=12)) => Self::TransitionMetal,
+            _ => Self::PostTransitionMetal,
+        }
+    }
+
+    /// The same as in `--dump`, e.g. “noble_gas”.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AlkaliMetal => "alkali_metal",
+            Self::AlkalineEarthMetal => "alkaline_earth_metal",
+            Self::Lanthanide => "lanthanide",
+            Self::Actinide => "actinide",
+            Self::TransitionMetal => "transition_metal",
+            Self::PostTransitionMetal => "post_transition_metal",
+            Self::Metalloid => "metalloid",
+            Self::ReactiveNonmetal => "reactive_nonmetal",
+            Self::Halogen => "halogen",
+            Self::NobleGas => "noble_gas",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Element {
+    /// Without any stable isotopes, including bismuth.
+    pub fn is_radioactive(&self) -> bool {
+        !self.isotopes.iter().any(Isotope::is_stable)
+    }
+
+    /// At standard pressure, `Unknown` where the transition temperatures are unknown.
+    pub fn phase_at(&self, temperature: f64) -> Phase {
+        match (self.melting_point, self.boiling_point) {
+            (_, Some(boiling)) if temperature >= boiling => Phase::Gas,
+            (Some(melting), _) if temperature < melting => Phase::Solid,
+            (Some(_), Some(_)) => Phase::Liquid,
+            // Helium doesn’t solidify at standard pressure, even at 0 K:
+            (None, Some(_)) if self.atomic_number == 2 => Phase::Liquid,
+            _ => Phase::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Solid,
+    Liquid,
+    Gas,
+    /// Elements from fermium on, never produced in bulk.
+    Unknown,
+}
+
+impl Phase {
+    fn at_stp(atomic_number: u8) -> Self {
+        match atomic_number {
+            1 | 2 | 7 | 8 | 9 | 10 | 17 | 18 | 36 | 54 | 86 => Self::Gas,
+            35 | 80 => Self::Liquid,
+            100.. => Self::Unknown,
+            _ => Self::Solid,
+        }
+    }
+
+    /// The same as in `--dump`, e.g. “gas”.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Solid => "solid",
+            Self::Liquid => "liquid",
+            Self::Gas => "gas",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AtomicWeight {
+    /// Abridged standard atomic weight or, for elements without one, the mass number of the
+    /// longest-lived isotope.
+    pub value: f64,
+    /// Interval of the standard atomic weight, for elements whose isotopic composition varies in
+    /// normal materials.
+    pub interval: Option<(f64, f64)>,
+    /// No stable isotopes and no characteristic terrestrial isotopic composition, so `value` is a
+    /// mass number, customarily printed in brackets, like `[98]`.
+    pub no_stable_isotopes: bool,
+    /// Decimal places `value` is printed with, since trailing zeros are significant, like in
+    /// `20.180`. Without it, the shortest representation is printed.
+    #[serde(default)]
+    pub decimals: Option<u8>,
+}
+
+impl AtomicWeight {
+    const fn standard(value: f64, decimals: u8) -> Self {
+        Self {
+            value,
+            interval: None,
+            no_stable_isotopes: false,
+            decimals: Some(decimals),
+        }
+    }
+
+    const fn interval(value: f64, decimals: u8, min: f64, max: f64) -> Self {
+        Self {
+            value,
+            interval: Some((min, max)),
+            no_stable_isotopes: false,
+            decimals: Some(decimals),
+        }
+    }
+
+    const fn mass_number(value: u16) -> Self {
+        Self {
+            value: value as f64,
+            interval: None,
+            no_stable_isotopes: true,
+            decimals: Some(0),
+        }
+    }
+}
+
+impl std::fmt::Display for AtomicWeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self.decimals {
+            Some(decimals) => format!("{:.*}", decimals as usize, self.value),
+            None => self.value.to_string(),
+        };
+        if self.no_stable_isotopes {
+            write!(f, "[{}]", value)
+        } else {
+            write!(f, "{}", value)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Discovery {
+    /// Negative for BCE.
+    pub year: i16,
+    /// Known since prehistoric or ancient times, so `year` is only a rough estimate.
+    pub antiquity: bool,
+    pub discoverer: Option<Cow<'static, str>>,
+    pub country: Option<Cow<'static, str>>,
+}
+
+impl Discovery {
+    const fn by(year: i16, discoverer: &'static str, country: &'static str) -> Self {
+        Self {
+            year,
+            antiquity: false,
+            discoverer: Some(Cow::Borrowed(discoverer)),
+            country: Some(Cow::Borrowed(country)),
+        }
+    }
+
+    const fn antiquity(year: i16) -> Self {
+        Self {
+            year,
+            antiquity: true,
+            discoverer: None,
+            country: None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Isotope {
+    pub mass_number: u16,
+    /// In % of atoms in nature, `None` where negligible.
+    pub abundance: Option<f64>,
+    /// In seconds, `None` for stable isotopes.
+    pub half_life: Option<f64>,
+    /// The main one, `None` for stable isotopes.
+    pub decay_mode: Option<DecayMode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecayMode {
+    #[serde(rename = "alpha")]
+    Alpha,
+    #[serde(rename = "beta-")]
+    BetaMinus,
+    /// Electron capture, also with β⁺ decay.
+    #[serde(rename = "ec")]
+    ElectronCapture,
+    #[serde(rename = "2beta-")]
+    DoubleBetaMinus,
+    #[serde(rename = "2ec")]
+    DoubleElectronCapture,
+    /// Spontaneous fission.
+    #[serde(rename = "sf")]
+    SpontaneousFission,
+}
+
+impl DecayMode {
+    const ALL: [Self; 6] = [
+        Self::Alpha,
+        Self::BetaMinus,
+        Self::ElectronCapture,
+        Self::DoubleBetaMinus,
+        Self::DoubleElectronCapture,
+        Self::SpontaneousFission,
+    ];
+
+    /// The same as in `--dump`, e.g. “beta-”.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Alpha => "alpha",
+            Self::BetaMinus => "beta-",
+            Self::ElectronCapture => "ec",
+            Self::DoubleBetaMinus => "2beta-",
+            Self::DoubleElectronCapture => "2ec",
+            Self::SpontaneousFission => "sf",
+        }
+    }
+}
+
+impl std::fmt::Display for DecayMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Isotope {
+    pub fn is_stable(&self) -> bool {
+        self.half_life.is_none()
+    }
+
+    /// Parses a space-separated list of `MASS_NUMBER[:ABUNDANCE][@HALF_LIFE/DECAY_MODE]`, e.g.
+    /// `"39:93.2581 40:0.0117@1.248e9y/beta- 41:6.7302"`.
+    fn parse_list(input: &str) -> Vec<Self> {
+        static RE: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r#"^(\d+)(?::([\d.]+))?(?:@([\d.e]+)(ms|s|m|h|d|y)/([\w+-]+))?$"#).unwrap()
+        });
+        input
+            .split_whitespace()
+            .map(|isotope| {
+                let cpt = RE
+                    .captures(isotope)
+                    .unwrap_or_else(|| panic!("malformed isotope: {}", isotope));
+                let number = |i| cpt.get(i).map(|a| a.as_str().parse::<f64>().unwrap());
+                let unit = match cpt.get(4).map(|a| a.as_str()) {
+                    Some("ms") => 0.001,
+                    Some("s") => 1.0,
+                    Some("m") => 60.0,
+                    Some("h") => 3600.0,
+                    Some("d") => 86400.0,
+                    // Julian year, as customary in nuclear data:
+                    _ => 365.25 * 86400.0,
+                };
+                Self {
+                    mass_number: cpt[1].parse().unwrap(),
+                    abundance: number(2),
+                    half_life: number(3).map(|a| a * unit),
+                    decay_mode: cpt.get(5).map(|a| {
+                        *DecayMode::ALL
+                            .iter()
+                            .find(|mode| mode.as_str() == a.as_str())
+                            .unwrap_or_else(|| panic!("unknown decay mode: {}", isotope))
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Subshell {
+    /// Principal quantum number.
+    pub n: u8,
+    /// Azimuthal quantum number: 0=s, 1=p, 2=d, 3=f, 4=g.
+    pub l: u8,
+}
+
+impl Subshell {
+    pub const LETTERS: &'static [char] = &['s', 'p', 'd', 'f', 'g'];
+
+    pub fn capacity(&self) -> u8 {
+        2 * (2 * self.l + 1)
+    }
+
+    /// Subshells in the order of filling according to the Madelung (n + l, n) rule.
+    fn madelung_order() -> impl Iterator<Item = Subshell> {
+        (1u8..).flat_map(|n_plus_l| {
+            (0..=(n_plus_l - 1) / 2)
+                .rev()
+                .filter(|&l| (l as usize) < Self::LETTERS.len())
+                .map(move |l| Subshell { n: n_plus_l - l, l })
+        })
+    }
+}
+
+impl std::fmt::Display for Subshell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.n, Self::LETTERS[self.l as usize])
+    }
+}
+
+/// Occupied subshells with their numbers of electrons, ordered by (n, l), as usually written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElectronConfiguration {
+    pub subshells: Vec<(Subshell, u8)>,
+}
+
+impl ElectronConfiguration {
+    /// Aufbau filling in the Madelung order, with the known (and, for superheavy elements,
+    /// predicted) exceptions applied.
+    pub fn ground_state(atomic_number: u8) -> Self {
+        let mut remaining = atomic_number;
+        let mut subshells = vec![];
+        for subshell in Subshell::madelung_order() {
+            if remaining == 0 {
+                break;
+            }
+            let electrons = remaining.min(subshell.capacity());
+            subshells.push((subshell, electrons));
+            remaining -= electrons;
+        }
+
+        if let Some((_, overrides)) = CONFIGURATION_EXCEPTIONS
+            .iter()
+            .find(|(z, _)| *z == atomic_number)
+        {
+            for &(n, l, electrons) in overrides.iter() {
+                let subshell = Subshell { n, l };
+                subshells.retain(|(s, _)| *s != subshell);
+                if electrons > 0 {
+                    subshells.push((subshell, electrons));
+                }
+            }
+        }
+
+        subshells.sort();
+        Self { subshells }
+    }
+
+    /// Ion with the given charge: electrons are removed from the highest n (and then l) first,
+    /// e.g. Fe²⁺ is `[Ar] 3d6`, and added in the Madelung order.
+    pub fn ion(atomic_number: u8, charge: i8) -> Result<Self, String> {
+        let electrons = atomic_number as i16 - charge as i16;
+        if !(0..=u8::MAX as i16).contains(&electrons) {
+            return Err(format!(
+                "An ion of Z={} can’t have the charge of {}.",
+                atomic_number, charge
+            ));
+        }
+
+        let mut config = Self::ground_state(atomic_number);
+        if charge > 0 {
+            let mut to_remove = charge as u8;
+            while to_remove > 0 {
+                let (_, last) = config.subshells.last_mut().unwrap();
+                let removed = to_remove.min(*last);
+                *last -= removed;
+                to_remove -= removed;
+                if *last == 0 {
+                    config.subshells.pop();
+                }
+            }
+        } else {
+            let mut to_add = charge.unsigned_abs();
+            for subshell in Subshell::madelung_order() {
+                if to_add == 0 {
+                    break;
+                }
+                let occupied = config
+                    .subshells
+                    .iter()
+                    .find(|(s, _)| *s == subshell)
+                    .map_or(0, |&(_, e)| e);
+                let added = to_add.min(subshell.capacity() - occupied);
+                if added > 0 {
+                    config.subshells.retain(|(s, _)| *s != subshell);
+                    config.subshells.push((subshell, occupied + added));
+                    to_add -= added;
+                }
+            }
+            config.subshells.sort();
+        }
+        Ok(config)
+    }
+
+    pub fn electrons(&self) -> u8 {
+        self.subshells.iter().map(|(_, e)| e).sum()
+    }
+
+    /// Occupancy of the outermost occupied subshell of the given type, e.g. 5 for the d of
+    /// chromium (3d⁵ 4s¹), or 0 if there’s none.
+    pub fn occupancy(&self, l: u8) -> u8 {
+        self.subshells
+            .iter()
+            .rev()
+            .find(|(s, _)| s.l == l)
+            .map_or(0, |&(_, e)| e)
+    }
+
+    /// Electrons in the shell with the highest principal quantum number, e.g. 1 for chromium.
+    pub fn valence_electrons(&self) -> u8 {
+        let max_n = self.subshells.iter().map(|(s, _)| s.n).max().unwrap_or(0);
+        self.subshells
+            .iter()
+            .filter(|(s, _)| s.n == max_n)
+            .map(|(_, e)| e)
+            .sum()
+    }
+
+    /// The inverse of `full`, e.g. from `1s2 2s2 2p6 3s1`.
+    pub fn parse(full: &str) -> Result<Self, String> {
+        static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^([1-9])([spdfg])(\d+)$"#).unwrap());
+        let mut subshells = full
+            .split_whitespace()
+            .map(|part| {
+                let error = || format!("invalid subshell: ‘{}’", part);
+                let cpt = RE.captures(part).ok_or_else(error)?;
+                let n: u8 = cpt[1].parse().unwrap();
+                let letter = cpt[2].chars().next().unwrap();
+                let l = Subshell::LETTERS.iter().position(|&a| a == letter).unwrap() as u8;
+                let subshell = Subshell { n, l };
+                let electrons: u8 = cpt[3].parse().map_err(|_| error())?;
+                if l >= n || electrons == 0 || electrons > subshell.capacity() {
+                    return Err(error());
+                }
+                Ok((subshell, electrons))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        subshells.sort();
+        if subshells.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(format!("repeated subshell in ‘{}’", full));
+        }
+        Ok(Self { subshells })
+    }
+
+    /// E.g. `1s2 2s2 2p6 3s2 3p6 3d5 4s1`.
+    pub fn full(&self) -> String {
+        Self::format(self.subshells.iter())
+    }
+
+    /// With the preceding noble gas core, e.g. `[Ar] 3d5 4s1`.
+    pub fn abbreviated(&self) -> String {
+        match self.noble_gas_core() {
+            None => self.full(),
+            Some((symbol, _)) => format!("[{}] {}", symbol, Self::format(self.valence().iter())),
+        }
+    }
+
+    /// Subshells outside of the noble gas core, i.e. the ones written out in the abbreviated
+    /// notation.
+    pub fn valence(&self) -> Vec<(Subshell, u8)> {
+        match self.noble_gas_core() {
+            None => self.subshells.clone(),
+            Some((_, core)) => self
+                .subshells
+                .iter()
+                .filter(|s| !core.subshells.contains(s))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn noble_gas_core(&self) -> Option<(&'static str, Self)> {
+        NOBLE_GASES
+            .iter()
+            .rev()
+            .map(|&(z, symbol)| (symbol, Self::ground_state(z)))
+            .find(|(_, core)| {
+                core.electrons() < self.electrons()
+                    && core.subshells.iter().all(|s| self.subshells.contains(s))
+            })
+    }
+
+    fn format<'a>(subshells: impl Iterator<Item = &'a (Subshell, u8)>) -> String {
+        subshells
+            .map(|(s, e)| format!("{}{}", s, e))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Serialize for ElectronConfiguration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut st = serializer.serialize_struct("ElectronConfiguration", 2)?;
+        st.serialize_field("full", &self.full())?;
+        st.serialize_field("abbreviated", &self.abbreviated())?;
+        st.end()
+    }
+}
+
+/// From `{full, abbreviated}`, where `abbreviated` is optional, but has to match if given.
+impl<'de> Deserialize<'de> for ElectronConfiguration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Raw {
+            full: String,
+            abbreviated: Option<String>,
+        }
+        let raw = Raw::deserialize(deserializer)?;
+        let config = Self::parse(&raw.full).map_err(D::Error::custom)?;
+        match raw.abbreviated {
+            Some(abbreviated) if abbreviated != config.abbreviated() => {
+                Err(D::Error::custom(format!(
+                    "abbreviated electron configuration ‘{}’ doesn’t match ‘{}’",
+                    abbreviated,
+                    config.abbreviated()
+                )))
+            }
+            _ => Ok(config),
+        }
+    }
+}
+
+static NOBLE_GASES: &[(u8, &str)] = &[
+    (2, "He"),
+    (10, "Ne"),
+    (18, "Ar"),
+    (36, "Kr"),
+    (54, "Xe"),
+    (86, "Rn"),
+    (118, "Og"),
+];
+
+/// `(n, l, electrons)`
+type SubshellOverride = (u8, u8, u8);
+
+/// Ground states that don’t follow the Madelung rule, as `(atomic_number, [(n, l, electrons)])`
+/// overriding the outer subshells. Source:
+/// <https://en.wikipedia.org/wiki/Electron_configurations_of_the_elements_(data_page)>.
+static CONFIGURATION_EXCEPTIONS: &[(u8, &[SubshellOverride])] = &[
+    (24, &[(3, 2, 5), (4, 0, 1)]),  // Cr
+    (29, &[(3, 2, 10), (4, 0, 1)]), // Cu
+    (41, &[(4, 2, 4), (5, 0, 1)]),  // Nb
+    (42, &[(4, 2, 5), (5, 0, 1)]),  // Mo
+    (44, &[(4, 2, 7), (5, 0, 1)]),  // Ru
+    (45, &[(4, 2, 8), (5, 0, 1)]),  // Rh
+    (46, &[(4, 2, 10), (5, 0, 0)]), // Pd
+    (47, &[(4, 2, 10), (5, 0, 1)]), // Ag
+    (57, &[(4, 3, 0), (5, 2, 1)]),  // La
+    (58, &[(4, 3, 1), (5, 2, 1)]),  // Ce
+    (64, &[(4, 3, 7), (5, 2, 1)]),  // Gd
+    (78, &[(5, 2, 9), (6, 0, 1)]),  // Pt
+    (79, &[(5, 2, 10), (6, 0, 1)]), // Au
+    (89, &[(5, 3, 0), (6, 2, 1)]),  // Ac
+    (90, &[(5, 3, 0), (6, 2, 2)]),  // Th
+    (91, &[(5, 3, 2), (6, 2, 1)]),  // Pa
+    (92, &[(5, 3, 3), (6, 2, 1)]),  // U
+    (93, &[(5, 3, 4), (6, 2, 1)]),  // Np
+    (96, &[(5, 3, 7), (6, 2, 1)]),  // Cm
+    (103, &[(6, 2, 0), (7, 1, 1)]), // Lr
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct OxidationStates {
     pub common: BTreeSet<i8>,
     pub notable: BTreeSet<i8>,
     pub predicted: BTreeSet<i8>,
     pub citation_needed: BTreeSet<i8>,
+    /// Citation IDs of each state, keys of `citations`.
+    pub sources: BTreeMap<i8, BTreeSet<String>>,
+    /// Wikitext of each cited reference, e.g. `{{cite book|…}}`, by its name in the template,
+    /// or `ref-N` if it’s unnamed.
+    pub citations: BTreeMap<String, String>,
 }
 
-pub static ALL: Lazy<Vec<Element>> = Lazy::new(|| {
-    SYMBOLS_IN_Z_ORDER
-        .into_iter()
-        .enumerate()
-        .map(|(idx, &symbol)| {
-            let atomic_number: u8 = (idx + 1).try_into().unwrap();
-
-            let (period, group) = match atomic_number {
-                1 => (1, Some(1)),
-                2 => (1, Some(18)),
-                3..=10 => (
-                    2,
-                    Some(match atomic_number {
-                        3 => 1,
-                        4 => 2,
-                        _ => atomic_number + 8,
-                    }),
-                ),
-                11..=18 => (
-                    3,
-                    Some(match atomic_number {
-                        11 => 1,
-                        12 => 2,
-                        _ => atomic_number - 0,
-                    }),
-                ),
-                19..=36 => (
-                    4,
-                    Some(match atomic_number {
-                        19 => 1,
-                        20 => 2,
-                        _ => atomic_number - 18,
-                    }),
-                ),
-                37..=54 => (
-                    5,
-                    Some(match atomic_number {
-                        37 => 1,
-                        38 => 2,
-                        _ => atomic_number - 36,
-                    }),
-                ),
-                55..=86 => (
-                    6,
-                    match atomic_number {
-                        55 => Some(1),
-                        56 => Some(2),
-                        57..=70 => None,
-                        _ => Some(atomic_number - 68),
-                    },
-                ),
-                87..=118 => (
-                    7,
-                    match atomic_number {
-                        87 => Some(1),
-                        88 => Some(2),
-                        89..=102 => None,
-                        _ => Some(atomic_number - 100),
-                    },
-                ),
-                _ => (0, None), // 0, 0 for invalid atomic numbers
-            };
-
-            let block = match (group, period, atomic_number) {
-                (Some(1..=2), _, _) | (_, _, 2) => 0,
-                (Some(13..=18), _, _) => 1,
-                (Some(3..=12), _, _) => 2,
-                (None, _, _) => 3,
-                _ => panic!("impossible"),
-            };
-
-            let symbol = Symbol(symbol.to_string());
-            let oxidation_states = OXIDATION_STATES.get(&symbol).unwrap().clone();
-
-            Element {
-                atomic_number,
-                symbol,
-                group,
-                period,
-                block,
-                oxidation_states,
+impl OxidationStates {
+    /// Every state belongs to at most one kind, the first of: common, notable, predicted and
+    /// citation_needed.
+    pub fn normalize(&mut self) {
+        self.notable.retain(|state| !self.common.contains(state));
+        self.predicted
+            .retain(|state| !self.common.contains(state) && !self.notable.contains(state));
+        self.citation_needed.retain(|state| {
+            !self.common.contains(state)
+                && !self.notable.contains(state)
+                && !self.predicted.contains(state)
+        });
+        let all: BTreeSet<i8> = (self.common.iter())
+            .chain(self.notable.iter())
+            .chain(self.predicted.iter())
+            .chain(self.citation_needed.iter())
+            .copied()
+            .collect();
+        self.sources.retain(|state, _| all.contains(state));
+        let cited: BTreeSet<&String> = self.sources.values().flatten().collect();
+        self.citations.retain(|id, _| cited.contains(id));
+    }
+}
+
+pub static ALL: Lazy<Vec<Element>> = Lazy::new(|| (1..=118).map(Element::new).collect());
+
+/// Hypothetical elements of period 8 for `--extended`, placed by the Madelung rule, with a
+/// g-block between the s- and f-blocks.
+pub static EXTENDED: Lazy<Vec<Element>> = Lazy::new(|| (119..=168).map(Element::new).collect());
+
+impl Element {
+    fn new(atomic_number: u8) -> Self {
+        let idx = atomic_number as usize - 1;
+
+        let (period, group) = match atomic_number {
+            1 => (1, Some(1)),
+            2 => (1, Some(18)),
+            3..=10 => (
+                2,
+                Some(match atomic_number {
+                    3 => 1,
+                    4 => 2,
+                    _ => atomic_number + 8,
+                }),
+            ),
+            11..=18 => (
+                3,
+                Some(match atomic_number {
+                    11 => 1,
+                    12 => 2,
+                    _ => atomic_number,
+                }),
+            ),
+            19..=36 => (
+                4,
+                Some(match atomic_number {
+                    19 => 1,
+                    20 => 2,
+                    _ => atomic_number - 18,
+                }),
+            ),
+            37..=54 => (
+                5,
+                Some(match atomic_number {
+                    37 => 1,
+                    38 => 2,
+                    _ => atomic_number - 36,
+                }),
+            ),
+            55..=86 => (
+                6,
+                match atomic_number {
+                    55 => Some(1),
+                    56 => Some(2),
+                    57..=70 => None,
+                    _ => Some(atomic_number - 68),
+                },
+            ),
+            87..=118 => (
+                7,
+                match atomic_number {
+                    87 => Some(1),
+                    88 => Some(2),
+                    89..=102 => None,
+                    _ => Some(atomic_number - 100),
+                },
+            ),
+            119..=168 => (
+                8,
+                match atomic_number {
+                    119 => Some(1),
+                    120 => Some(2),
+                    121..=152 => None,
+                    _ => Some(atomic_number - 150),
+                },
+            ),
+            _ => (0, None), // 0, 0 for invalid atomic numbers
+        };
+
+        let block = match (group, period, atomic_number) {
+            (Some(1..=2), _, _) | (_, _, 2) => 0,
+            (Some(13..=18), _, _) => 1,
+            (Some(3..=12), _, _) => 2,
+            (None, _, 121..=138) => 4,
+            (None, _, _) => 3,
+            _ => panic!("impossible"),
+        };
+
+        let (symbol, name) = match SYMBOLS_IN_Z_ORDER.get(idx) {
+            Some(&symbol) => {
+                let symbol = Symbol(symbol.to_string());
+                let name = crate::l10n::element_name(&symbol, crate::l10n::Lang::En)
+                    .unwrap()
+                    .to_string();
+                (symbol, name)
             }
-        })
-        .collect()
-});
+            None => Self::systematic_name(atomic_number),
+        };
+        let category = Category::of(atomic_number, group);
+        let atomic_weight = ATOMIC_WEIGHTS_IN_Z_ORDER.get(idx).copied();
+        let electron_configuration = ElectronConfiguration::ground_state(atomic_number);
+        let electronegativity = ELECTRONEGATIVITIES_IN_Z_ORDER.get(idx).copied().flatten();
+        let covalent_radius_pm = COVALENT_RADII_IN_Z_ORDER.get(idx).copied().flatten();
+        let van_der_waals_radius_pm = VAN_DER_WAALS_RADII_IN_Z_ORDER.get(idx).copied().flatten();
+        let ionization_energy = IonizationEnergies {
+            first_kj_per_mol: FIRST_IONIZATION_ENERGIES_IN_Z_ORDER
+                .get(idx)
+                .copied()
+                .flatten(),
+        };
+        let electron_affinity_kj_per_mol =
+            ELECTRON_AFFINITIES_IN_Z_ORDER.get(idx).copied().flatten();
+        let phase = Phase::at_stp(atomic_number);
+        let melting_point = MELTING_POINTS_IN_Z_ORDER.get(idx).copied().flatten();
+        let boiling_point = BOILING_POINTS_IN_Z_ORDER.get(idx).copied().flatten();
+        let discovery = DISCOVERIES_IN_Z_ORDER.get(idx).cloned();
+        let isotopes = ISOTOPES_IN_Z_ORDER
+            .get(idx)
+            .map_or(vec![], |isotopes| Isotope::parse_list(isotopes));
+        // Predictions for some of period 8 are in the same Wikipedia template:
+        let oxidation_states = OXIDATION_STATES.get(&symbol).cloned().unwrap_or_default();
+
+        Element {
+            atomic_number,
+            symbol,
+            name,
+            local_name: None,
+            group,
+            period,
+            block,
+            category,
+            atomic_weight,
+            electron_configuration,
+            electronegativity,
+            covalent_radius_pm,
+            van_der_waals_radius_pm,
+            ionization_energy,
+            electron_affinity_kj_per_mol,
+            phase,
+            phase_at_t: phase,
+            melting_point,
+            boiling_point,
+            discovery,
+            isotopes,
+            oxidation_states,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// `local_name`, or `name` if it isn’t localized.
+    pub fn display_name(&self) -> &str {
+        self.local_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// IUPAC systematic name and symbol, e.g. “Ununennium” and “Uue” for 119.
+    fn systematic_name(atomic_number: u8) -> (Symbol, String) {
+        const ROOTS: [&str; 10] = [
+            "nil", "un", "bi", "tri", "quad", "pent", "hex", "sept", "oct", "enn",
+        ];
+        let roots: Vec<&str> = atomic_number
+            .to_string()
+            .chars()
+            .map(|digit| ROOTS[digit.to_digit(10).unwrap() as usize])
+            .collect();
+        // “bi” + “ium” is “bium”, and “enn” + “nil” is “ennil”:
+        let name = format!("{}ium", roots.concat())
+            .replace("iium", "ium")
+            .replace("nnn", "nn");
+        let capitalize = |text: &str| {
+            let mut chars = text.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        };
+        let symbol: String = roots.iter().map(|root| &root[..1]).collect();
+        (Symbol(capitalize(&symbol)), capitalize(&name))
+    }
+}
 
-static SYMBOLS_IN_Z_ORDER: &'static [&'static str] = &[
+static SYMBOLS_IN_Z_ORDER: &[&str] = &[
     "H", "He", "Li", "Be", "B", "C", "N", "O", "F", "Ne", "Na", "Mg", "Al", "Si", "P", "S", "Cl",
     "Ar", "K", "Ca", "Sc", "Ti", "V", "Cr", "Mn", "Fe", "Co", "Ni", "Cu", "Zn", "Ga", "Ge", "As",
     "Se", "Br", "Kr", "Rb", "Sr", "Y", "Zr", "Nb", "Mo", "Tc", "Ru", "Rh", "Pd", "Ag", "Cd", "In",
@@ -128,7 +902,1372 @@ static SYMBOLS_IN_Z_ORDER: &'static [&'static str] = &[
     "Fl", "Mc", "Lv", "Ts", "Og",
 ];
 
-pub static OXIDATION_STATES: Lazy<HashMap<Symbol, OxidationStates>> = Lazy::new(|| {
+/// Source: IUPAC Commission on Isotopic Abundances and Atomic Weights, abridged standard atomic
+/// weights (2021) <https://iupac.qmul.ac.uk/AtWt/>. For elements without a standard atomic weight,
+/// the mass number of the longest-lived isotope.
+static ATOMIC_WEIGHTS_IN_Z_ORDER: &[AtomicWeight] = &[
+    AtomicWeight::interval(1.0080, 4, 1.00784, 1.00811), // H
+    AtomicWeight::standard(4.0026, 4),                   // He
+    AtomicWeight::interval(6.94, 2, 6.938, 6.997),       // Li
+    AtomicWeight::standard(9.0122, 4),                   // Be
+    AtomicWeight::interval(10.81, 2, 10.806, 10.821),    // B
+    AtomicWeight::interval(12.011, 3, 12.0096, 12.0116), // C
+    AtomicWeight::interval(14.007, 3, 14.00643, 14.00728), // N
+    AtomicWeight::interval(15.999, 3, 15.99903, 15.99977), // O
+    AtomicWeight::standard(18.998, 3),                   // F
+    AtomicWeight::standard(20.180, 3),                   // Ne
+    AtomicWeight::standard(22.990, 3),                   // Na
+    AtomicWeight::interval(24.305, 3, 24.304, 24.307),   // Mg
+    AtomicWeight::standard(26.982, 3),                   // Al
+    AtomicWeight::interval(28.085, 3, 28.084, 28.086),   // Si
+    AtomicWeight::standard(30.974, 3),                   // P
+    AtomicWeight::interval(32.06, 2, 32.059, 32.076),    // S
+    AtomicWeight::interval(35.45, 2, 35.446, 35.457),    // Cl
+    AtomicWeight::interval(39.95, 2, 39.792, 39.963),    // Ar
+    AtomicWeight::standard(39.098, 3),                   // K
+    AtomicWeight::standard(40.078, 3),                   // Ca
+    AtomicWeight::standard(44.956, 3),                   // Sc
+    AtomicWeight::standard(47.867, 3),                   // Ti
+    AtomicWeight::standard(50.942, 3),                   // V
+    AtomicWeight::standard(51.996, 3),                   // Cr
+    AtomicWeight::standard(54.938, 3),                   // Mn
+    AtomicWeight::standard(55.845, 3),                   // Fe
+    AtomicWeight::standard(58.933, 3),                   // Co
+    AtomicWeight::standard(58.693, 3),                   // Ni
+    AtomicWeight::standard(63.546, 3),                   // Cu
+    AtomicWeight::standard(65.38, 2),                    // Zn
+    AtomicWeight::standard(69.723, 3),                   // Ga
+    AtomicWeight::standard(72.630, 3),                   // Ge
+    AtomicWeight::standard(74.922, 3),                   // As
+    AtomicWeight::standard(78.971, 3),                   // Se
+    AtomicWeight::interval(79.904, 3, 79.901, 79.907),   // Br
+    AtomicWeight::standard(83.798, 3),                   // Kr
+    AtomicWeight::standard(85.468, 3),                   // Rb
+    AtomicWeight::standard(87.62, 2),                    // Sr
+    AtomicWeight::standard(88.906, 3),                   // Y
+    AtomicWeight::standard(91.224, 3),                   // Zr
+    AtomicWeight::standard(92.906, 3),                   // Nb
+    AtomicWeight::standard(95.95, 2),                    // Mo
+    AtomicWeight::mass_number(98),                       // Tc
+    AtomicWeight::standard(101.07, 2),                   // Ru
+    AtomicWeight::standard(102.91, 2),                   // Rh
+    AtomicWeight::standard(106.42, 2),                   // Pd
+    AtomicWeight::standard(107.87, 2),                   // Ag
+    AtomicWeight::standard(112.41, 2),                   // Cd
+    AtomicWeight::standard(114.82, 2),                   // In
+    AtomicWeight::standard(118.71, 2),                   // Sn
+    AtomicWeight::standard(121.76, 2),                   // Sb
+    AtomicWeight::standard(127.60, 2),                   // Te
+    AtomicWeight::standard(126.90, 2),                   // I
+    AtomicWeight::standard(131.29, 2),                   // Xe
+    AtomicWeight::standard(132.91, 2),                   // Cs
+    AtomicWeight::standard(137.33, 2),                   // Ba
+    AtomicWeight::standard(138.91, 2),                   // La
+    AtomicWeight::standard(140.12, 2),                   // Ce
+    AtomicWeight::standard(140.91, 2),                   // Pr
+    AtomicWeight::standard(144.24, 2),                   // Nd
+    AtomicWeight::mass_number(145),                      // Pm
+    AtomicWeight::standard(150.36, 2),                   // Sm
+    AtomicWeight::standard(151.96, 2),                   // Eu
+    AtomicWeight::standard(157.25, 2),                   // Gd
+    AtomicWeight::standard(158.93, 2),                   // Tb
+    AtomicWeight::standard(162.50, 2),                   // Dy
+    AtomicWeight::standard(164.93, 2),                   // Ho
+    AtomicWeight::standard(167.26, 2),                   // Er
+    AtomicWeight::standard(168.93, 2),                   // Tm
+    AtomicWeight::standard(173.05, 2),                   // Yb
+    AtomicWeight::standard(174.97, 2),                   // Lu
+    AtomicWeight::standard(178.49, 2),                   // Hf
+    AtomicWeight::standard(180.95, 2),                   // Ta
+    AtomicWeight::standard(183.84, 2),                   // W
+    AtomicWeight::standard(186.21, 2),                   // Re
+    AtomicWeight::standard(190.23, 2),                   // Os
+    AtomicWeight::standard(192.22, 2),                   // Ir
+    AtomicWeight::standard(195.08, 2),                   // Pt
+    AtomicWeight::standard(196.97, 2),                   // Au
+    AtomicWeight::standard(200.59, 2),                   // Hg
+    AtomicWeight::interval(204.38, 2, 204.382, 204.385), // Tl
+    AtomicWeight::interval(207.2, 1, 206.14, 207.94),    // Pb
+    AtomicWeight::standard(208.98, 2),                   // Bi
+    AtomicWeight::mass_number(209),                      // Po
+    AtomicWeight::mass_number(210),                      // At
+    AtomicWeight::mass_number(222),                      // Rn
+    AtomicWeight::mass_number(223),                      // Fr
+    AtomicWeight::mass_number(226),                      // Ra
+    AtomicWeight::mass_number(227),                      // Ac
+    AtomicWeight::standard(232.04, 2),                   // Th
+    AtomicWeight::standard(231.04, 2),                   // Pa
+    AtomicWeight::standard(238.03, 2),                   // U
+    AtomicWeight::mass_number(237),                      // Np
+    AtomicWeight::mass_number(244),                      // Pu
+    AtomicWeight::mass_number(243),                      // Am
+    AtomicWeight::mass_number(247),                      // Cm
+    AtomicWeight::mass_number(247),                      // Bk
+    AtomicWeight::mass_number(251),                      // Cf
+    AtomicWeight::mass_number(252),                      // Es
+    AtomicWeight::mass_number(257),                      // Fm
+    AtomicWeight::mass_number(258),                      // Md
+    AtomicWeight::mass_number(259),                      // No
+    AtomicWeight::mass_number(266),                      // Lr
+    AtomicWeight::mass_number(267),                      // Rf
+    AtomicWeight::mass_number(268),                      // Db
+    AtomicWeight::mass_number(269),                      // Sg
+    AtomicWeight::mass_number(270),                      // Bh
+    AtomicWeight::mass_number(269),                      // Hs
+    AtomicWeight::mass_number(278),                      // Mt
+    AtomicWeight::mass_number(281),                      // Ds
+    AtomicWeight::mass_number(282),                      // Rg
+    AtomicWeight::mass_number(285),                      // Cn
+    AtomicWeight::mass_number(286),                      // Nh
+    AtomicWeight::mass_number(289),                      // Fl
+    AtomicWeight::mass_number(290),                      // Mc
+    AtomicWeight::mass_number(293),                      // Lv
+    AtomicWeight::mass_number(294),                      // Ts
+    AtomicWeight::mass_number(294),                      // Og
+];
+
+/// Pauling electronegativities. Source:
+/// <https://en.wikipedia.org/wiki/Electronegativities_of_the_elements_(data_page)>.
+static ELECTRONEGATIVITIES_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(2.20), // H
+    None,       // He
+    Some(0.98), // Li
+    Some(1.57), // Be
+    Some(2.04), // B
+    Some(2.55), // C
+    Some(3.04), // N
+    Some(3.44), // O
+    Some(3.98), // F
+    None,       // Ne
+    Some(0.93), // Na
+    Some(1.31), // Mg
+    Some(1.61), // Al
+    Some(1.90), // Si
+    Some(2.19), // P
+    Some(2.58), // S
+    Some(3.16), // Cl
+    None,       // Ar
+    Some(0.82), // K
+    Some(1.00), // Ca
+    Some(1.36), // Sc
+    Some(1.54), // Ti
+    Some(1.63), // V
+    Some(1.66), // Cr
+    Some(1.55), // Mn
+    Some(1.83), // Fe
+    Some(1.88), // Co
+    Some(1.91), // Ni
+    Some(1.90), // Cu
+    Some(1.65), // Zn
+    Some(1.81), // Ga
+    Some(2.01), // Ge
+    Some(2.18), // As
+    Some(2.55), // Se
+    Some(2.96), // Br
+    Some(3.00), // Kr
+    Some(0.82), // Rb
+    Some(0.95), // Sr
+    Some(1.22), // Y
+    Some(1.33), // Zr
+    Some(1.6),  // Nb
+    Some(2.16), // Mo
+    Some(1.9),  // Tc
+    Some(2.2),  // Ru
+    Some(2.28), // Rh
+    Some(2.20), // Pd
+    Some(1.93), // Ag
+    Some(1.69), // Cd
+    Some(1.78), // In
+    Some(1.96), // Sn
+    Some(2.05), // Sb
+    Some(2.1),  // Te
+    Some(2.66), // I
+    Some(2.6),  // Xe
+    Some(0.79), // Cs
+    Some(0.89), // Ba
+    Some(1.10), // La
+    Some(1.12), // Ce
+    Some(1.13), // Pr
+    Some(1.14), // Nd
+    Some(1.13), // Pm
+    Some(1.17), // Sm
+    Some(1.2),  // Eu
+    Some(1.2),  // Gd
+    Some(1.1),  // Tb
+    Some(1.22), // Dy
+    Some(1.23), // Ho
+    Some(1.24), // Er
+    Some(1.25), // Tm
+    Some(1.1),  // Yb
+    Some(1.27), // Lu
+    Some(1.3),  // Hf
+    Some(1.5),  // Ta
+    Some(2.36), // W
+    Some(1.9),  // Re
+    Some(2.2),  // Os
+    Some(2.20), // Ir
+    Some(2.28), // Pt
+    Some(2.54), // Au
+    Some(2.00), // Hg
+    Some(1.62), // Tl
+    Some(2.33), // Pb
+    Some(2.02), // Bi
+    Some(2.0),  // Po
+    Some(2.2),  // At
+    Some(2.2),  // Rn
+    Some(0.79), // Fr
+    Some(0.9),  // Ra
+    Some(1.1),  // Ac
+    Some(1.3),  // Th
+    Some(1.5),  // Pa
+    Some(1.38), // U
+    Some(1.36), // Np
+    Some(1.28), // Pu
+    Some(1.13), // Am
+    Some(1.28), // Cm
+    Some(1.3),  // Bk
+    Some(1.3),  // Cf
+    Some(1.3),  // Es
+    Some(1.3),  // Fm
+    Some(1.3),  // Md
+    Some(1.3),  // No
+    Some(1.3),  // Lr
+    None,       // Rf
+    None,       // Db
+    None,       // Sg
+    None,       // Bh
+    None,       // Hs
+    None,       // Mt
+    None,       // Ds
+    None,       // Rg
+    None,       // Cn
+    None,       // Nh
+    None,       // Fl
+    None,       // Mc
+    None,       // Lv
+    None,       // Ts
+    None,       // Og
+];
+
+/// Single-bond covalent radii in pm, for carbon sp³, and for Mn, Fe, Co low spin. Source:
+/// Cordero et al., “Covalent radii revisited”, Dalton Trans., 2008, 2832–2838,
+/// <https://doi.org/10.1039/B801115J>.
+static COVALENT_RADII_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(31.0),  // H
+    Some(28.0),  // He
+    Some(128.0), // Li
+    Some(96.0),  // Be
+    Some(84.0),  // B
+    Some(76.0),  // C
+    Some(71.0),  // N
+    Some(66.0),  // O
+    Some(57.0),  // F
+    Some(58.0),  // Ne
+    Some(166.0), // Na
+    Some(141.0), // Mg
+    Some(121.0), // Al
+    Some(111.0), // Si
+    Some(107.0), // P
+    Some(105.0), // S
+    Some(102.0), // Cl
+    Some(106.0), // Ar
+    Some(203.0), // K
+    Some(176.0), // Ca
+    Some(170.0), // Sc
+    Some(160.0), // Ti
+    Some(153.0), // V
+    Some(139.0), // Cr
+    Some(139.0), // Mn
+    Some(132.0), // Fe
+    Some(126.0), // Co
+    Some(124.0), // Ni
+    Some(132.0), // Cu
+    Some(122.0), // Zn
+    Some(122.0), // Ga
+    Some(120.0), // Ge
+    Some(119.0), // As
+    Some(120.0), // Se
+    Some(120.0), // Br
+    Some(116.0), // Kr
+    Some(220.0), // Rb
+    Some(195.0), // Sr
+    Some(190.0), // Y
+    Some(175.0), // Zr
+    Some(164.0), // Nb
+    Some(154.0), // Mo
+    Some(147.0), // Tc
+    Some(146.0), // Ru
+    Some(142.0), // Rh
+    Some(139.0), // Pd
+    Some(145.0), // Ag
+    Some(144.0), // Cd
+    Some(142.0), // In
+    Some(139.0), // Sn
+    Some(139.0), // Sb
+    Some(138.0), // Te
+    Some(139.0), // I
+    Some(140.0), // Xe
+    Some(244.0), // Cs
+    Some(215.0), // Ba
+    Some(207.0), // La
+    Some(204.0), // Ce
+    Some(203.0), // Pr
+    Some(201.0), // Nd
+    Some(199.0), // Pm
+    Some(198.0), // Sm
+    Some(198.0), // Eu
+    Some(196.0), // Gd
+    Some(194.0), // Tb
+    Some(192.0), // Dy
+    Some(192.0), // Ho
+    Some(189.0), // Er
+    Some(190.0), // Tm
+    Some(187.0), // Yb
+    Some(187.0), // Lu
+    Some(175.0), // Hf
+    Some(170.0), // Ta
+    Some(162.0), // W
+    Some(151.0), // Re
+    Some(144.0), // Os
+    Some(141.0), // Ir
+    Some(136.0), // Pt
+    Some(136.0), // Au
+    Some(132.0), // Hg
+    Some(145.0), // Tl
+    Some(146.0), // Pb
+    Some(148.0), // Bi
+    Some(140.0), // Po
+    Some(150.0), // At
+    Some(150.0), // Rn
+    Some(260.0), // Fr
+    Some(221.0), // Ra
+    Some(215.0), // Ac
+    Some(206.0), // Th
+    Some(200.0), // Pa
+    Some(196.0), // U
+    Some(190.0), // Np
+    Some(187.0), // Pu
+    Some(180.0), // Am
+    Some(169.0), // Cm
+    None,        // Bk
+    None,        // Cf
+    None,        // Es
+    None,        // Fm
+    None,        // Md
+    None,        // No
+    None,        // Lr
+    None,        // Rf
+    None,        // Db
+    None,        // Sg
+    None,        // Bh
+    None,        // Hs
+    None,        // Mt
+    None,        // Ds
+    None,        // Rg
+    None,        // Cn
+    None,        // Nh
+    None,        // Fl
+    None,        // Mc
+    None,        // Lv
+    None,        // Ts
+    None,        // Og
+];
+
+/// Van der Waals radii in pm. Source: Bondi, J. Phys. Chem., 1964, 68, 441–451,
+/// <https://doi.org/10.1021/j100785a001>, completed for main-group elements by Mantina et al.,
+/// J. Phys. Chem. A, 2009, 113, 5806–5812, <https://doi.org/10.1021/jp8111556>.
+static VAN_DER_WAALS_RADII_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(120.0), // H
+    Some(140.0), // He
+    Some(182.0), // Li
+    Some(153.0), // Be
+    Some(192.0), // B
+    Some(170.0), // C
+    Some(155.0), // N
+    Some(152.0), // O
+    Some(147.0), // F
+    Some(154.0), // Ne
+    Some(227.0), // Na
+    Some(173.0), // Mg
+    Some(184.0), // Al
+    Some(210.0), // Si
+    Some(180.0), // P
+    Some(180.0), // S
+    Some(175.0), // Cl
+    Some(188.0), // Ar
+    Some(275.0), // K
+    Some(231.0), // Ca
+    None,        // Sc
+    None,        // Ti
+    None,        // V
+    None,        // Cr
+    None,        // Mn
+    None,        // Fe
+    None,        // Co
+    Some(163.0), // Ni
+    Some(140.0), // Cu
+    Some(139.0), // Zn
+    Some(187.0), // Ga
+    Some(211.0), // Ge
+    Some(185.0), // As
+    Some(190.0), // Se
+    Some(185.0), // Br
+    Some(202.0), // Kr
+    Some(303.0), // Rb
+    Some(249.0), // Sr
+    None,        // Y
+    None,        // Zr
+    None,        // Nb
+    None,        // Mo
+    None,        // Tc
+    None,        // Ru
+    None,        // Rh
+    Some(163.0), // Pd
+    Some(172.0), // Ag
+    Some(158.0), // Cd
+    Some(193.0), // In
+    Some(217.0), // Sn
+    Some(206.0), // Sb
+    Some(206.0), // Te
+    Some(198.0), // I
+    Some(216.0), // Xe
+    Some(343.0), // Cs
+    Some(268.0), // Ba
+    None,        // La
+    None,        // Ce
+    None,        // Pr
+    None,        // Nd
+    None,        // Pm
+    None,        // Sm
+    None,        // Eu
+    None,        // Gd
+    None,        // Tb
+    None,        // Dy
+    None,        // Ho
+    None,        // Er
+    None,        // Tm
+    None,        // Yb
+    None,        // Lu
+    None,        // Hf
+    None,        // Ta
+    None,        // W
+    None,        // Re
+    None,        // Os
+    None,        // Ir
+    Some(175.0), // Pt
+    Some(166.0), // Au
+    Some(155.0), // Hg
+    Some(196.0), // Tl
+    Some(202.0), // Pb
+    Some(207.0), // Bi
+    Some(197.0), // Po
+    Some(202.0), // At
+    Some(220.0), // Rn
+    Some(348.0), // Fr
+    Some(283.0), // Ra
+    None,        // Ac
+    None,        // Th
+    None,        // Pa
+    Some(186.0), // U
+    None,        // Np
+    None,        // Pu
+    None,        // Am
+    None,        // Cm
+    None,        // Bk
+    None,        // Cf
+    None,        // Es
+    None,        // Fm
+    None,        // Md
+    None,        // No
+    None,        // Lr
+    None,        // Rf
+    None,        // Db
+    None,        // Sg
+    None,        // Bh
+    None,        // Hs
+    None,        // Mt
+    None,        // Ds
+    None,        // Rg
+    None,        // Cn
+    None,        // Nh
+    None,        // Fl
+    None,        // Mc
+    None,        // Lv
+    None,        // Ts
+    None,        // Og
+];
+
+/// First ionization energies in kJ/mol, rounded to 0.1. Source: CRC Handbook of Chemistry and
+/// Physics and the NIST Atomic Spectra Database, via
+/// <https://en.wikipedia.org/wiki/Ionization_energies_of_the_elements_(data_page)>.
+static FIRST_IONIZATION_ENERGIES_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(1312.0), // H
+    Some(2372.3), // He
+    Some(520.2),  // Li
+    Some(899.5),  // Be
+    Some(800.6),  // B
+    Some(1086.5), // C
+    Some(1402.3), // N
+    Some(1313.9), // O
+    Some(1681.0), // F
+    Some(2080.7), // Ne
+    Some(495.8),  // Na
+    Some(737.7),  // Mg
+    Some(577.5),  // Al
+    Some(786.5),  // Si
+    Some(1011.8), // P
+    Some(999.6),  // S
+    Some(1251.2), // Cl
+    Some(1520.6), // Ar
+    Some(418.8),  // K
+    Some(589.8),  // Ca
+    Some(633.1),  // Sc
+    Some(658.8),  // Ti
+    Some(650.9),  // V
+    Some(652.9),  // Cr
+    Some(717.3),  // Mn
+    Some(762.5),  // Fe
+    Some(760.4),  // Co
+    Some(737.1),  // Ni
+    Some(745.5),  // Cu
+    Some(906.4),  // Zn
+    Some(578.8),  // Ga
+    Some(762.0),  // Ge
+    Some(947.0),  // As
+    Some(941.0),  // Se
+    Some(1139.9), // Br
+    Some(1350.8), // Kr
+    Some(403.0),  // Rb
+    Some(549.5),  // Sr
+    Some(600.0),  // Y
+    Some(640.1),  // Zr
+    Some(652.1),  // Nb
+    Some(684.3),  // Mo
+    Some(702.0),  // Tc
+    Some(710.2),  // Ru
+    Some(719.7),  // Rh
+    Some(804.4),  // Pd
+    Some(731.0),  // Ag
+    Some(867.8),  // Cd
+    Some(558.3),  // In
+    Some(708.6),  // Sn
+    Some(834.0),  // Sb
+    Some(869.3),  // Te
+    Some(1008.4), // I
+    Some(1170.4), // Xe
+    Some(375.7),  // Cs
+    Some(502.9),  // Ba
+    Some(538.1),  // La
+    Some(534.4),  // Ce
+    Some(527.0),  // Pr
+    Some(533.1),  // Nd
+    Some(540.0),  // Pm
+    Some(544.5),  // Sm
+    Some(547.1),  // Eu
+    Some(593.4),  // Gd
+    Some(565.8),  // Tb
+    Some(573.0),  // Dy
+    Some(581.0),  // Ho
+    Some(589.3),  // Er
+    Some(596.7),  // Tm
+    Some(603.4),  // Yb
+    Some(523.5),  // Lu
+    Some(658.5),  // Hf
+    Some(761.0),  // Ta
+    Some(770.0),  // W
+    Some(760.0),  // Re
+    Some(840.0),  // Os
+    Some(880.0),  // Ir
+    Some(870.0),  // Pt
+    Some(890.1),  // Au
+    Some(1007.1), // Hg
+    Some(589.4),  // Tl
+    Some(715.6),  // Pb
+    Some(703.0),  // Bi
+    Some(812.1),  // Po
+    Some(899.0),  // At
+    Some(1037.0), // Rn
+    Some(393.0),  // Fr
+    Some(509.3),  // Ra
+    Some(499.0),  // Ac
+    Some(587.0),  // Th
+    Some(568.0),  // Pa
+    Some(597.6),  // U
+    Some(604.5),  // Np
+    Some(584.7),  // Pu
+    Some(578.0),  // Am
+    Some(581.0),  // Cm
+    Some(601.0),  // Bk
+    Some(608.0),  // Cf
+    Some(619.0),  // Es
+    Some(627.0),  // Fm
+    Some(635.0),  // Md
+    Some(642.0),  // No
+    Some(478.6),  // Lr
+    None,         // Rf
+    None,         // Db
+    None,         // Sg
+    None,         // Bh
+    None,         // Hs
+    None,         // Mt
+    None,         // Ds
+    None,         // Rg
+    None,         // Cn
+    None,         // Nh
+    None,         // Fl
+    None,         // Mc
+    None,         // Lv
+    None,         // Ts
+    None,         // Og
+];
+
+/// Electron affinities in kJ/mol, rounded to 0.1, only where measured, `None` also for
+/// elements without a stable anion. Source:
+/// <https://en.wikipedia.org/wiki/Electron_affinity_(data_page)>.
+static ELECTRON_AFFINITIES_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(72.8),  // H
+    None,        // He
+    Some(59.6),  // Li
+    None,        // Be
+    Some(27.0),  // B
+    Some(121.8), // C
+    None,        // N
+    Some(141.0), // O
+    Some(328.2), // F
+    None,        // Ne
+    Some(52.9),  // Na
+    None,        // Mg
+    Some(41.8),  // Al
+    Some(134.1), // Si
+    Some(72.0),  // P
+    Some(200.4), // S
+    Some(348.6), // Cl
+    None,        // Ar
+    Some(48.4),  // K
+    Some(2.4),   // Ca
+    Some(17.3),  // Sc
+    Some(7.3),   // Ti
+    Some(50.9),  // V
+    Some(65.2),  // Cr
+    None,        // Mn
+    Some(14.8),  // Fe
+    Some(63.9),  // Co
+    Some(111.7), // Ni
+    Some(119.2), // Cu
+    None,        // Zn
+    Some(41.5),  // Ga
+    Some(118.9), // Ge
+    Some(78.5),  // As
+    Some(195.0), // Se
+    Some(324.5), // Br
+    None,        // Kr
+    Some(46.9),  // Rb
+    Some(5.0),   // Sr
+    Some(29.6),  // Y
+    Some(41.8),  // Zr
+    Some(88.5),  // Nb
+    Some(72.1),  // Mo
+    None,        // Tc
+    Some(101.0), // Ru
+    Some(110.3), // Rh
+    Some(54.2),  // Pd
+    Some(125.9), // Ag
+    None,        // Cd
+    Some(37.0),  // In
+    Some(107.3), // Sn
+    Some(101.1), // Sb
+    Some(190.2), // Te
+    Some(295.2), // I
+    None,        // Xe
+    Some(45.5),  // Cs
+    Some(14.0),  // Ba
+    Some(53.8),  // La
+    Some(55.0),  // Ce
+    Some(10.5),  // Pr
+    Some(9.4),   // Nd
+    None,        // Pm
+    None,        // Sm
+    Some(11.2),  // Eu
+    Some(13.2),  // Gd
+    Some(12.7),  // Tb
+    None,        // Dy
+    None,        // Ho
+    None,        // Er
+    Some(99.3),  // Tm
+    None,        // Yb
+    Some(23.0),  // Lu
+    Some(17.2),  // Hf
+    Some(31.2),  // Ta
+    Some(78.8),  // W
+    Some(5.8),   // Re
+    Some(104.0), // Os
+    Some(150.9), // Ir
+    Some(205.0), // Pt
+    Some(222.7), // Au
+    None,        // Hg
+    Some(30.9),  // Tl
+    Some(34.4),  // Pb
+    Some(90.9),  // Bi
+    None,        // Po
+    Some(233.1), // At
+    None,        // Rn
+    None,        // Fr
+    None,        // Ra
+    None,        // Ac
+    Some(112.7), // Th
+    None,        // Pa
+    Some(30.4),  // U
+    None,        // Np
+    None,        // Pu
+    None,        // Am
+    None,        // Cm
+    None,        // Bk
+    None,        // Cf
+    None,        // Es
+    None,        // Fm
+    None,        // Md
+    None,        // No
+    None,        // Lr
+    None,        // Rf
+    None,        // Db
+    None,        // Sg
+    None,        // Bh
+    None,        // Hs
+    None,        // Mt
+    None,        // Ds
+    None,        // Rg
+    None,        // Cn
+    None,        // Nh
+    None,        // Fl
+    None,        // Mc
+    None,        // Lv
+    None,        // Ts
+    None,        // Og
+];
+
+/// Melting points in K at standard pressure, and the sublimation point for C and As. `None` for
+/// helium, which doesn’t solidify at standard pressure, and where only estimates exist. Source:
+/// <https://en.wikipedia.org/wiki/Melting_points_of_the_elements_(data_page)>.
+static MELTING_POINTS_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(13.99),   // H
+    None,          // He
+    Some(453.65),  // Li
+    Some(1560.0),  // Be
+    Some(2349.0),  // B
+    Some(3915.0),  // C
+    Some(63.15),   // N
+    Some(54.36),   // O
+    Some(53.48),   // F
+    Some(24.56),   // Ne
+    Some(370.94),  // Na
+    Some(923.0),   // Mg
+    Some(933.47),  // Al
+    Some(1687.0),  // Si
+    Some(317.3),   // P
+    Some(388.36),  // S
+    Some(171.6),   // Cl
+    Some(83.81),   // Ar
+    Some(336.7),   // K
+    Some(1115.0),  // Ca
+    Some(1814.0),  // Sc
+    Some(1941.0),  // Ti
+    Some(2183.0),  // V
+    Some(2180.0),  // Cr
+    Some(1519.0),  // Mn
+    Some(1811.0),  // Fe
+    Some(1768.0),  // Co
+    Some(1728.0),  // Ni
+    Some(1357.77), // Cu
+    Some(692.68),  // Zn
+    Some(302.91),  // Ga
+    Some(1211.4),  // Ge
+    Some(887.0),   // As
+    Some(494.0),   // Se
+    Some(265.8),   // Br
+    Some(115.78),  // Kr
+    Some(312.45),  // Rb
+    Some(1050.0),  // Sr
+    Some(1799.0),  // Y
+    Some(2128.0),  // Zr
+    Some(2750.0),  // Nb
+    Some(2896.0),  // Mo
+    Some(2430.0),  // Tc
+    Some(2607.0),  // Ru
+    Some(2237.0),  // Rh
+    Some(1828.05), // Pd
+    Some(1234.93), // Ag
+    Some(594.22),  // Cd
+    Some(429.75),  // In
+    Some(505.08),  // Sn
+    Some(903.78),  // Sb
+    Some(722.66),  // Te
+    Some(386.85),  // I
+    Some(161.4),   // Xe
+    Some(301.59),  // Cs
+    Some(1000.0),  // Ba
+    Some(1193.0),  // La
+    Some(1068.0),  // Ce
+    Some(1208.0),  // Pr
+    Some(1297.0),  // Nd
+    Some(1315.0),  // Pm
+    Some(1345.0),  // Sm
+    Some(1099.0),  // Eu
+    Some(1585.0),  // Gd
+    Some(1629.0),  // Tb
+    Some(1680.0),  // Dy
+    Some(1734.0),  // Ho
+    Some(1802.0),  // Er
+    Some(1818.0),  // Tm
+    Some(1097.0),  // Yb
+    Some(1925.0),  // Lu
+    Some(2506.0),  // Hf
+    Some(3290.0),  // Ta
+    Some(3695.0),  // W
+    Some(3459.0),  // Re
+    Some(3306.0),  // Os
+    Some(2719.0),  // Ir
+    Some(2041.4),  // Pt
+    Some(1337.33), // Au
+    Some(234.43),  // Hg
+    Some(577.0),   // Tl
+    Some(600.61),  // Pb
+    Some(544.7),   // Bi
+    Some(527.0),   // Po
+    None,          // At
+    Some(202.0),   // Rn
+    None,          // Fr
+    Some(973.0),   // Ra
+    Some(1323.0),  // Ac
+    Some(2023.0),  // Th
+    Some(1841.0),  // Pa
+    Some(1405.3),  // U
+    Some(912.0),   // Np
+    Some(912.5),   // Pu
+    Some(1449.0),  // Am
+    Some(1613.0),  // Cm
+    Some(1259.0),  // Bk
+    Some(1173.0),  // Cf
+    Some(1133.0),  // Es
+    None,          // Fm
+    None,          // Md
+    None,          // No
+    None,          // Lr
+    None,          // Rf
+    None,          // Db
+    None,          // Sg
+    None,          // Bh
+    None,          // Hs
+    None,          // Mt
+    None,          // Ds
+    None,          // Rg
+    None,          // Cn
+    None,          // Nh
+    None,          // Fl
+    None,          // Mc
+    None,          // Lv
+    None,          // Ts
+    None,          // Og
+];
+
+/// Boiling points in K at standard pressure, and the sublimation point for C and As. `None` where
+/// only estimates exist. Source:
+/// <https://en.wikipedia.org/wiki/Boiling_points_of_the_elements_(data_page)>.
+static BOILING_POINTS_IN_Z_ORDER: &[Option<f64>] = &[
+    Some(20.27),   // H
+    Some(4.22),    // He
+    Some(1603.0),  // Li
+    Some(2742.0),  // Be
+    Some(4200.0),  // B
+    Some(3915.0),  // C
+    Some(77.36),   // N
+    Some(90.19),   // O
+    Some(85.03),   // F
+    Some(27.1),    // Ne
+    Some(1156.09), // Na
+    Some(1363.0),  // Mg
+    Some(2743.0),  // Al
+    Some(3538.0),  // Si
+    Some(553.7),   // P
+    Some(717.8),   // S
+    Some(239.11),  // Cl
+    Some(87.3),    // Ar
+    Some(1032.0),  // K
+    Some(1757.0),  // Ca
+    Some(3109.0),  // Sc
+    Some(3560.0),  // Ti
+    Some(3680.0),  // V
+    Some(2944.0),  // Cr
+    Some(2334.0),  // Mn
+    Some(3134.0),  // Fe
+    Some(3200.0),  // Co
+    Some(3003.0),  // Ni
+    Some(2835.0),  // Cu
+    Some(1180.0),  // Zn
+    Some(2673.0),  // Ga
+    Some(3106.0),  // Ge
+    Some(887.0),   // As
+    Some(958.0),   // Se
+    Some(332.0),   // Br
+    Some(119.93),  // Kr
+    Some(961.0),   // Rb
+    Some(1650.0),  // Sr
+    Some(3203.0),  // Y
+    Some(4650.0),  // Zr
+    Some(5017.0),  // Nb
+    Some(4912.0),  // Mo
+    Some(4538.0),  // Tc
+    Some(4423.0),  // Ru
+    Some(3968.0),  // Rh
+    Some(3236.0),  // Pd
+    Some(2435.0),  // Ag
+    Some(1040.0),  // Cd
+    Some(2345.0),  // In
+    Some(2875.0),  // Sn
+    Some(1908.0),  // Sb
+    Some(1261.0),  // Te
+    Some(457.4),   // I
+    Some(165.05),  // Xe
+    Some(944.0),   // Cs
+    Some(2118.0),  // Ba
+    Some(3737.0),  // La
+    Some(3716.0),  // Ce
+    Some(3403.0),  // Pr
+    Some(3347.0),  // Nd
+    Some(3273.0),  // Pm
+    Some(2173.0),  // Sm
+    Some(1802.0),  // Eu
+    Some(3546.0),  // Gd
+    Some(3396.0),  // Tb
+    Some(2840.0),  // Dy
+    Some(2873.0),  // Ho
+    Some(3141.0),  // Er
+    Some(2223.0),  // Tm
+    Some(1469.0),  // Yb
+    Some(3675.0),  // Lu
+    Some(4876.0),  // Hf
+    Some(5731.0),  // Ta
+    Some(6203.0),  // W
+    Some(5869.0),  // Re
+    Some(5285.0),  // Os
+    Some(4403.0),  // Ir
+    Some(4098.0),  // Pt
+    Some(3243.0),  // Au
+    Some(629.88),  // Hg
+    Some(1746.0),  // Tl
+    Some(2022.0),  // Pb
+    Some(1837.0),  // Bi
+    Some(1235.0),  // Po
+    None,          // At
+    Some(211.5),   // Rn
+    None,          // Fr
+    Some(2010.0),  // Ra
+    Some(3471.0),  // Ac
+    Some(5061.0),  // Th
+    Some(4300.0),  // Pa
+    Some(4404.0),  // U
+    Some(4447.0),  // Np
+    Some(3505.0),  // Pu
+    Some(2880.0),  // Am
+    Some(3383.0),  // Cm
+    Some(2900.0),  // Bk
+    Some(1743.0),  // Cf
+    None,          // Es
+    None,          // Fm
+    None,          // Md
+    None,          // No
+    None,          // Lr
+    None,          // Rf
+    None,          // Db
+    None,          // Sg
+    None,          // Bh
+    None,          // Hs
+    None,          // Mt
+    None,          // Ds
+    None,          // Rg
+    None,          // Cn
+    None,          // Nh
+    None,          // Fl
+    None,          // Mc
+    None,          // Lv
+    None,          // Ts
+    None,          // Og
+];
+
+/// Year of the first recognition as an element (not necessarily of isolation), and by whom,
+/// with today’s country names. Years of elements known since antiquity are rough estimates.
+/// Source: <https://en.wikipedia.org/wiki/Discovery_of_chemical_elements>.
+static DISCOVERIES_IN_Z_ORDER: &[Discovery] = &[
+    Discovery::by(1766, "Henry Cavendish", "United Kingdom"), // H
+    Discovery::by(
+        1868,
+        "Pierre Janssen, Norman Lockyer",
+        "France, United Kingdom",
+    ), // He
+    Discovery::by(1817, "Johan August Arfwedson", "Sweden"),  // Li
+    Discovery::by(1798, "Louis Nicolas Vauquelin", "France"), // Be
+    Discovery::by(
+        1808,
+        "Joseph Louis Gay-Lussac, Louis Jacques Thénard, Humphry Davy",
+        "France, United Kingdom",
+    ), // B
+    Discovery::antiquity(-3750),                              // C
+    Discovery::by(1772, "Daniel Rutherford", "United Kingdom"), // N
+    Discovery::by(
+        1771,
+        "Carl Wilhelm Scheele, Joseph Priestley",
+        "Sweden, United Kingdom",
+    ), // O
+    Discovery::by(1810, "André-Marie Ampère", "France"),      // F
+    Discovery::by(1898, "William Ramsay, Morris Travers", "United Kingdom"), // Ne
+    Discovery::by(1807, "Humphry Davy", "United Kingdom"),    // Na
+    Discovery::by(1755, "Joseph Black", "United Kingdom"),    // Mg
+    Discovery::by(1825, "Hans Christian Ørsted", "Denmark"),  // Al
+    Discovery::by(1823, "Jöns Jacob Berzelius", "Sweden"),    // Si
+    Discovery::by(1669, "Hennig Brand", "Germany"),           // P
+    Discovery::antiquity(-2000),                              // S
+    Discovery::by(1774, "Carl Wilhelm Scheele", "Sweden"),    // Cl
+    Discovery::by(1894, "Lord Rayleigh, William Ramsay", "United Kingdom"), // Ar
+    Discovery::by(1807, "Humphry Davy", "United Kingdom"),    // K
+    Discovery::by(1808, "Humphry Davy", "United Kingdom"),    // Ca
+    Discovery::by(1879, "Lars Fredrik Nilson", "Sweden"),     // Sc
+    Discovery::by(1791, "William Gregor", "United Kingdom"),  // Ti
+    Discovery::by(1801, "Andrés Manuel del Río", "Mexico"),   // V
+    Discovery::by(1797, "Louis Nicolas Vauquelin", "France"), // Cr
+    Discovery::by(1774, "Johan Gottlieb Gahn", "Sweden"),     // Mn
+    Discovery::antiquity(-5000),                              // Fe
+    Discovery::by(1735, "Georg Brandt", "Sweden"),            // Co
+    Discovery::by(1751, "Axel Fredrik Cronstedt", "Sweden"),  // Ni
+    Discovery::antiquity(-9000),                              // Cu
+    Discovery::by(1746, "Andreas Sigismund Marggraf", "Germany"), // Zn
+    Discovery::by(1875, "Paul-Émile Lecoq de Boisbaudran", "France"), // Ga
+    Discovery::by(1886, "Clemens Winkler", "Germany"),        // Ge
+    Discovery::by(1250, "Albertus Magnus", "Germany"),        // As
+    Discovery::by(1817, "Jöns Jacob Berzelius", "Sweden"),    // Se
+    Discovery::by(
+        1825,
+        "Antoine Jérôme Balard, Carl Jacob Löwig",
+        "France, Germany",
+    ), // Br
+    Discovery::by(1898, "William Ramsay, Morris Travers", "United Kingdom"), // Kr
+    Discovery::by(1861, "Robert Bunsen, Gustav Kirchhoff", "Germany"), // Rb
+    Discovery::by(1790, "Adair Crawford", "United Kingdom"),  // Sr
+    Discovery::by(1794, "Johan Gadolin", "Finland"),          // Y
+    Discovery::by(1789, "Martin Heinrich Klaproth", "Germany"), // Zr
+    Discovery::by(1801, "Charles Hatchett", "United Kingdom"), // Nb
+    Discovery::by(1778, "Carl Wilhelm Scheele", "Sweden"),    // Mo
+    Discovery::by(1937, "Carlo Perrier, Emilio Segrè", "Italy"), // Tc
+    Discovery::by(1844, "Karl Ernst Claus", "Russia"),        // Ru
+    Discovery::by(1804, "William Hyde Wollaston", "United Kingdom"), // Rh
+    Discovery::by(1802, "William Hyde Wollaston", "United Kingdom"), // Pd
+    Discovery::antiquity(-5000),                              // Ag
+    Discovery::by(1817, "Friedrich Stromeyer", "Germany"),    // Cd
+    Discovery::by(
+        1863,
+        "Ferdinand Reich, Hieronymous Theodor Richter",
+        "Germany",
+    ), // In
+    Discovery::antiquity(-3500),                              // Sn
+    Discovery::antiquity(-3000),                              // Sb
+    Discovery::by(1782, "Franz-Joseph Müller von Reichenstein", "Austria"), // Te
+    Discovery::by(1811, "Bernard Courtois", "France"),        // I
+    Discovery::by(1898, "William Ramsay, Morris Travers", "United Kingdom"), // Xe
+    Discovery::by(1860, "Robert Bunsen, Gustav Kirchhoff", "Germany"), // Cs
+    Discovery::by(1772, "Carl Wilhelm Scheele", "Sweden"),    // Ba
+    Discovery::by(1838, "Carl Gustaf Mosander", "Sweden"),    // La
+    Discovery::by(
+        1803,
+        "Jöns Jacob Berzelius, Wilhelm Hisinger, Martin Heinrich Klaproth",
+        "Sweden, Germany",
+    ), // Ce
+    Discovery::by(1885, "Carl Auer von Welsbach", "Austria"), // Pr
+    Discovery::by(1885, "Carl Auer von Welsbach", "Austria"), // Nd
+    Discovery::by(
+        1945,
+        "Jacob A. Marinsky, Lawrence E. Glendenin, Charles D. Coryell",
+        "United States",
+    ), // Pm
+    Discovery::by(1879, "Paul-Émile Lecoq de Boisbaudran", "France"), // Sm
+    Discovery::by(1896, "Eugène-Anatole Demarçay", "France"), // Eu
+    Discovery::by(1880, "Jean Charles Galissard de Marignac", "Switzerland"), // Gd
+    Discovery::by(1843, "Carl Gustaf Mosander", "Sweden"),    // Tb
+    Discovery::by(1886, "Paul-Émile Lecoq de Boisbaudran", "France"), // Dy
+    Discovery::by(
+        1878,
+        "Marc Delafontaine, Jacques-Louis Soret",
+        "Switzerland",
+    ), // Ho
+    Discovery::by(1843, "Carl Gustaf Mosander", "Sweden"),    // Er
+    Discovery::by(1879, "Per Teodor Cleve", "Sweden"),        // Tm
+    Discovery::by(1878, "Jean Charles Galissard de Marignac", "Switzerland"), // Yb
+    Discovery::by(
+        1907,
+        "Georges Urbain, Carl Auer von Welsbach, Charles James",
+        "France, Austria, United States",
+    ), // Lu
+    Discovery::by(1922, "Dirk Coster, George de Hevesy", "Denmark"), // Hf
+    Discovery::by(1802, "Anders Gustaf Ekeberg", "Sweden"),   // Ta
+    Discovery::by(1781, "Carl Wilhelm Scheele", "Sweden"),    // W
+    Discovery::by(1925, "Walter Noddack, Ida Noddack, Otto Berg", "Germany"), // Re
+    Discovery::by(1803, "Smithson Tennant", "United Kingdom"), // Os
+    Discovery::by(1803, "Smithson Tennant", "United Kingdom"), // Ir
+    Discovery::by(1735, "Antonio de Ulloa", "Spain"),         // Pt
+    Discovery::antiquity(-6000),                              // Au
+    Discovery::antiquity(-2000),                              // Hg
+    Discovery::by(1861, "William Crookes", "United Kingdom"), // Tl
+    Discovery::antiquity(-7000),                              // Pb
+    Discovery::by(1753, "Claude François Geoffroy", "France"), // Bi
+    Discovery::by(1898, "Pierre Curie, Marie Curie", "France"), // Po
+    Discovery::by(
+        1940,
+        "Dale R. Corson, Kenneth Ross MacKenzie, Emilio Segrè",
+        "United States",
+    ), // At
+    Discovery::by(1899, "Ernest Rutherford, Robert B. Owens", "Canada"), // Rn
+    Discovery::by(1939, "Marguerite Perey", "France"),        // Fr
+    Discovery::by(1898, "Pierre Curie, Marie Curie", "France"), // Ra
+    Discovery::by(1899, "André-Louis Debierne", "France"),    // Ac
+    Discovery::by(1829, "Jöns Jacob Berzelius", "Sweden"),    // Th
+    Discovery::by(1913, "Kasimir Fajans, Oswald Helmuth Göhring", "Germany"), // Pa
+    Discovery::by(1789, "Martin Heinrich Klaproth", "Germany"), // U
+    Discovery::by(1940, "Edwin McMillan, Philip H. Abelson", "United States"), // Np
+    Discovery::by(
+        1940,
+        "Glenn T. Seaborg, Arthur Wahl, Joseph W. Kennedy, Edwin McMillan",
+        "United States",
+    ), // Pu
+    Discovery::by(
+        1944,
+        "Glenn T. Seaborg, Ralph A. James, Leon O. Morgan, Albert Ghiorso",
+        "United States",
+    ), // Am
+    Discovery::by(
+        1944,
+        "Glenn T. Seaborg, Ralph A. James, Albert Ghiorso",
+        "United States",
+    ), // Cm
+    Discovery::by(
+        1949,
+        "Stanley G. Thompson, Albert Ghiorso, Glenn T. Seaborg",
+        "United States",
+    ), // Bk
+    Discovery::by(
+        1950,
+        "Stanley G. Thompson, Kenneth Street Jr., Albert Ghiorso, Glenn T. Seaborg",
+        "United States",
+    ), // Cf
+    Discovery::by(1952, "Albert Ghiorso et al.", "United States"), // Es
+    Discovery::by(1952, "Albert Ghiorso et al.", "United States"), // Fm
+    Discovery::by(
+        1955,
+        "Albert Ghiorso, Bernard G. Harvey, Gregory Choppin, Stanley G. Thompson, Glenn T. Seaborg",
+        "United States",
+    ), // Md
+    Discovery::by(1966, "Georgy Flerov et al.", "Soviet Union"), // No
+    Discovery::by(1961, "Albert Ghiorso et al.", "United States"), // Lr
+    Discovery::by(
+        1964,
+        "Joint Institute for Nuclear Research, Lawrence Berkeley Laboratory",
+        "Soviet Union, United States",
+    ), // Rf
+    Discovery::by(
+        1968,
+        "Joint Institute for Nuclear Research, Lawrence Berkeley Laboratory",
+        "Soviet Union, United States",
+    ), // Db
+    Discovery::by(1974, "Lawrence Berkeley Laboratory", "United States"), // Sg
+    Discovery::by(
+        1981,
+        "GSI Helmholtz Centre for Heavy Ion Research",
+        "Germany",
+    ), // Bh
+    Discovery::by(
+        1984,
+        "GSI Helmholtz Centre for Heavy Ion Research",
+        "Germany",
+    ), // Hs
+    Discovery::by(
+        1982,
+        "GSI Helmholtz Centre for Heavy Ion Research",
+        "Germany",
+    ), // Mt
+    Discovery::by(
+        1994,
+        "GSI Helmholtz Centre for Heavy Ion Research",
+        "Germany",
+    ), // Ds
+    Discovery::by(
+        1994,
+        "GSI Helmholtz Centre for Heavy Ion Research",
+        "Germany",
+    ), // Rg
+    Discovery::by(
+        1996,
+        "GSI Helmholtz Centre for Heavy Ion Research",
+        "Germany",
+    ), // Cn
+    Discovery::by(2004, "RIKEN", "Japan"),                    // Nh
+    Discovery::by(
+        1999,
+        "Joint Institute for Nuclear Research, Lawrence Livermore National Laboratory",
+        "Russia, United States",
+    ), // Fl
+    Discovery::by(
+        2003,
+        "Joint Institute for Nuclear Research, Lawrence Livermore National Laboratory",
+        "Russia, United States",
+    ), // Mc
+    Discovery::by(
+        2000,
+        "Joint Institute for Nuclear Research, Lawrence Livermore National Laboratory",
+        "Russia, United States",
+    ), // Lv
+    Discovery::by(
+        2010,
+        "Joint Institute for Nuclear Research, Oak Ridge National Laboratory",
+        "Russia, United States",
+    ), // Ts
+    Discovery::by(
+        2002,
+        "Joint Institute for Nuclear Research, Lawrence Livermore National Laboratory",
+        "Russia, United States",
+    ), // Og
+];
+
+/// Stable and naturally occurring isotopes, as `MASS_NUMBER[:ABUNDANCE][@HALF_LIFE/DECAY_MODE]`,
+/// with abundances in % of the IUPAC representative isotopic composition, and the main decay mode.
+/// For elements without stable isotopes, the longest-lived ones (half-lives of superheavy ones are
+/// very uncertain), and a few well-known radioisotopes otherwise. Sources: abundances from
+/// CIAAW, Meija et al., “Isotopic compositions of the elements 2013”, Pure Appl. Chem., 2016, 88,
+/// 293–306, <https://doi.org/10.1515/pac-2015-0503>, via <https://ciaaw.org/isotopic-abundances.htm>;
+/// half-lives and decay modes from NUBASE2020, Kondev et al., Chinese Phys. C, 2021, 45, 030001,
+/// <https://doi.org/10.1088/1674-1137/abddae>, via NuDat 3 <https://www.nndc.bnl.gov/nudat3/>.
+static ISOTOPES_IN_Z_ORDER: &[&str] = &[
+    "1:99.9885 2:0.0115 3@12.32y/beta-", // H
+    "3:0.000134 4:99.999866", // He
+    "6:7.59 7:92.41", // Li
+    "9:100", // Be
+    "10:19.9 11:80.1", // B
+    "12:98.93 13:1.07 14@5700y/beta-", // C
+    "14:99.636 15:0.364", // N
+    "16:99.757 17:0.038 18:0.205", // O
+    "19:100", // F
+    "20:90.48 21:0.27 22:9.25", // Ne
+    "23:100", // Na
+    "24:78.99 25:10.00 26:11.01", // Mg
+    "27:100", // Al
+    "28:92.223 29:4.685 30:3.092", // Si
+    "31:100", // P
+    "32:94.99 33:0.75 34:4.25 36:0.01", // S
+    "35:75.76 37:24.24", // Cl
+    "36:0.3336 38:0.0629 40:99.6035", // Ar
+    "39:93.2581 40:0.0117@1.248e9y/beta- 41:6.7302", // K
+    "40:96.941 42:0.647 43:0.135 44:2.086 46:0.004 48:0.187@6.4e19y/2beta-", // Ca
+    "45:100", // Sc
+    "46:8.25 47:7.44 48:73.72 49:5.41 50:5.18", // Ti
+    "50:0.250@2.7e17y/ec 51:99.750", // V
+    "50:4.345 52:83.789 53:9.501 54:2.365", // Cr
+    "55:100", // Mn
+    "54:5.845 56:91.754 57:2.119 58:0.282", // Fe
+    "59:100 60@5.2714y/beta-", // Co
+    "58:68.077 60:26.223 61:1.1399 62:3.6346 64:0.9255", // Ni
+    "63:69.15 65:30.85", // Cu
+    "64:49.17 66:27.73 67:4.04 68:18.45 70:0.61", // Zn
+    "69:60.108 71:39.892", // Ga
+    "70:20.52 72:27.45 73:7.76 74:36.52 76:7.75@1.88e21y/2beta-", // Ge
+    "75:100", // As
+    "74:0.86 76:9.23 77:7.60 78:23.69 80:49.80 82:8.82@1.08e20y/2beta-", // Se
+    "79:50.69 81:49.31", // Br
+    "78:0.355@9.2e21y/2ec 80:2.286 82:11.593 83:11.500 84:56.987 86:17.279", // Kr
+    "85:72.17 87:27.83@4.97e10y/beta-", // Rb
+    "84:0.56 86:9.86 87:7.00 88:82.58 90@28.79y/beta-", // Sr
+    "89:100", // Y
+    "90:51.45 91:11.22 92:17.15 94:17.38 96:2.80@2.0e19y/2beta-", // Zr
+    "93:100", // Nb
+    "92:14.53 94:9.15 95:15.84 96:16.67 97:9.60 98:24.39 100:9.82@7.1e18y/2beta-", // Mo
+    "97@4.21e6y/ec 98@4.2e6y/beta- 99@2.111e5y/beta-", // Tc
+    "96:5.54 98:1.87 99:12.76 100:12.60 101:17.06 102:31.55 104:18.62", // Ru
+    "103:100", // Rh
+    "102:1.02 104:11.14 105:22.33 106:27.33 108:26.46 110:11.72", // Pd
+    "107:51.839 109:48.161", // Ag
+    "106:1.25 108:0.89 110:12.49 111:12.80 112:24.13 113:12.22@7.7e15y/beta- 114:28.73 116:7.49@3.1e19y/2beta-", // Cd
+    "113:4.29 115:95.71@4.41e14y/beta-", // In
+    "112:0.97 114:0.66 115:0.34 116:14.54 117:7.68 118:24.22 119:8.59 120:32.58 122:4.63 124:5.79", // Sn
+    "121:57.21 123:42.79", // Sb
+    "120:0.09 122:2.55 123:0.89 124:4.74 125:7.07 126:18.84 128:31.74@2.2e24y/2beta- 130:34.08@7.9e20y/2beta-", // Te
+    "127:100 131@8.0252d/beta-", // I
+    "124:0.0952@1.8e22y/2ec 126:0.0890 128:1.9102 129:26.4006 130:4.0710 131:21.2324 132:26.9086 134:10.4357 136:8.8573@2.165e21y/2beta-", // Xe
+    "133:100 137@30.08y/beta-", // Cs
+    "130:0.106 132:0.101 134:2.417 135:6.592 136:7.854 137:11.232 138:71.698", // Ba
+    "138:0.08881@1.02e11y/ec 139:99.91119", // La
+    "136:0.186 138:0.251 140:88.449 142:11.114", // Ce
+    "141:100", // Pr
+    "142:27.152 143:12.174 144:23.798@2.29e15y/alpha 145:8.293 146:17.189 148:5.756 150:5.638@9.3e18y/2beta-", // Nd
+    "145@17.7y/ec 146@5.53y/ec 147@2.6234y/beta-", // Pm
+    "144:3.07 147:14.99@1.06e11y/alpha 148:11.24@7e15y/alpha 149:13.82 150:7.38 152:26.75 154:22.75", // Sm
+    "151:47.81@5e18y/alpha 153:52.19", // Eu
+    "152:0.20@1.08e14y/alpha 154:2.18 155:14.80 156:20.47 157:15.65 158:24.84 160:21.86", // Gd
+    "159:100", // Tb
+    "156:0.056 158:0.095 160:2.329 161:18.889 162:25.475 163:24.896 164:28.260", // Dy
+    "165:100", // Ho
+    "162:0.139 164:1.601 166:33.503 167:22.869 168:26.978 170:14.910", // Er
+    "169:100", // Tm
+    "168:0.123 170:2.982 171:14.09 172:21.68 173:16.103 174:32.026 176:12.996", // Yb
+    "175:97.401 176:2.599@3.76e10y/beta-", // Lu
+    "174:0.16@2.0e15y/alpha 176:5.26 177:18.60 178:27.28 179:13.62 180:35.08", // Hf
+    "180:0.01201 181:99.98799", // Ta
+    "180:0.12@1.8e18y/alpha 182:26.50 183:14.31 184:30.64 186:28.43", // W
+    "185:37.40 187:62.60@4.12e10y/beta-", // Re
+    "184:0.02 186:1.59@2.0e15y/alpha 187:1.96 188:13.24 189:16.15 190:26.26 192:40.78", // Os
+    "191:37.3 193:62.7", // Ir
+    "190:0.012@4.8e11y/alpha 192:0.782 194:32.86 195:33.78 196:25.21 198:7.356", // Pt
+    "197:100", // Au
+    "196:0.15 198:9.97 199:16.87 200:23.10 201:13.18 202:29.86 204:6.87", // Hg
+    "203:29.52 205:70.48", // Tl
+    "204:1.4 206:24.1 207:22.1 208:52.4", // Pb
+    "209:100@2.01e19y/alpha", // Bi
+    "208@2.898y/alpha 209@124y/alpha 210@138.376d/alpha", // Po
+    "210@8.1h/ec 211@7.214h/ec", // At
+    "211@14.6h/ec 222@3.8235d/alpha", // Rn
+    "223@22.00m/beta-", // Fr
+    "226@1600y/alpha 228@5.75y/beta-", // Ra
+    "227@21.772y/beta-", // Ac
+    "230@7.54e4y/alpha 232:100@1.40e10y/alpha", // Th
+    "231:100@3.276e4y/alpha", // Pa
+    "234:0.0054@2.455e5y/alpha 235:0.7204@7.04e8y/alpha 238:99.2742@4.468e9y/alpha", // U
+    "237@2.144e6y/alpha", // Np
+    "239@24110y/alpha 244@8.13e7y/alpha", // Pu
+    "241@432.2y/alpha 243@7370y/alpha", // Am
+    "247@1.56e7y/alpha 248@3.48e5y/alpha", // Cm
+    "247@1380y/alpha", // Bk
+    "251@898y/alpha", // Cf
+    "252@471.7d/alpha", // Es
+    "257@100.5d/alpha", // Fm
+    "258@51.5d/alpha", // Md
+    "259@58m/alpha", // No
+    "266@11h/sf", // Lr
+    "267@1.3h/sf", // Rf
+    "268@16h/sf", // Db
+    "269@14m/alpha", // Sg
+    "270@1m/alpha", // Bh
+    "269@16s/alpha", // Hs
+    "278@4.5s/alpha", // Mt
+    "281@14s/sf", // Ds
+    "282@100s/alpha", // Rg
+    "285@28s/alpha", // Cn
+    "286@9.5s/alpha", // Nh
+    "289@1.9s/alpha", // Fl
+    "290@0.65s/alpha", // Mc
+    "293@57ms/alpha", // Lv
+    "294@51ms/alpha", // Ts
+    "294@0.69ms/alpha", // Og
+];
+
+pub static OXIDATION_STATES: Lazy<HashMap<Symbol, OxidationStates>> =
+    Lazy::new(|| parse_oxidation_states(OXIDATION_STATES_WIKIPEDIA).unwrap());
+
+/// Parses the wikitext of `Template:Infobox element/symbol-to-oxidation-state`, e.g. a newer copy
+/// of `OXIDATION_STATES_WIKIPEDIA`. Errors point at the line of `wikitext` that wasn’t understood.
+pub fn parse_oxidation_states(wikitext: &str) -> Result<HashMap<Symbol, OxidationStates>, String> {
     #[derive(PartialEq)]
     enum Ctx {
         None,
@@ -140,27 +2279,59 @@ pub static OXIDATION_STATES: Lazy<HashMap<Symbol, OxidationStates>> = Lazy::new(
 
     let re_comments = Regex::new(r#"(?ms)<!--.*?-->"#).unwrap();
     let re_sup = Regex::new(r#"(?ms),?\s*<sup>\s*\?\s*</sup>"#).unwrap();
-    let re_refs = Regex::new(r#"(?ms)<ref(\s|>).*?</ref>"#).unwrap();
+    let re_refs = Regex::new(r#"(?ms)<ref(\s[^>]*?)?\s*(?:/>|>(.*?)</ref>)"#).unwrap();
+    let re_ref_name = Regex::new(r#"name\s*=\s*(?:"([^"]*)"|([^\s"/>]+))"#).unwrap();
     let re_noinclude = Regex::new(r#"(?ms)<noinclude>.*?</noinclude>"#).unwrap();
     let re_default = Regex::new(r#"(?m)^\|#default=.*$"#).unwrap();
     let re_infobox = Regex::new(r#"(?m)\{\{Infobox .*$"#).unwrap();
 
-    let sources = OXIDATION_STATES_WIKIPEDIA;
-    let sources = sources.replace("−", "-"); // “−” U+2212 Minus Sign
-    let sources = re_comments.replace_all(&sources, "");
-    let sources = re_sup.replace_all(&sources, "?");
-    let sources = re_refs.replace_all(&sources, "");
-    let sources = re_noinclude.replace_all(&sources, "");
-    let sources = re_default.replace_all(&sources, "");
-    let sources = re_infobox.replace_all(&sources, "");
+    // Refs are replaced with `\0N\0` markers, N being the index in `ref_ids`, which cannot be
+    // confused with text like `#5`. Named refs can be reused with `<ref name=… />`, also before
+    // their definition:
+    let mut ref_ids: Vec<String> = vec![];
+    let mut citations: HashMap<String, String> = HashMap::new();
+    let mut unnamed = 0;
+    let mut ref_marker = |ref_: &str| {
+        let cpt = re_refs.captures(ref_).unwrap();
+        let name = cpt
+            .get(1)
+            .and_then(|attrs| re_ref_name.captures(attrs.as_str()))
+            .and_then(|name| name.get(1).or(name.get(2)))
+            .map(|name| name.as_str().to_string());
+        let id = name.unwrap_or_else(|| {
+            unnamed += 1;
+            format!("ref-{}", unnamed)
+        });
+        if let Some(body) = cpt.get(2) {
+            citations
+                .entry(id.clone())
+                .or_insert_with(|| body.as_str().trim().to_string());
+        }
+        ref_ids.push(id);
+        format!("\0{}\0", ref_ids.len() - 1)
+    };
+
+    let sources = wikitext.replace("−", "-"); // “−” U+2212 Minus Sign
+    let sources = replace_keeping_lines(&re_comments, &sources, |_| String::new());
+    let sources = replace_keeping_lines(&re_sup, &sources, |_| "?".to_string());
+    let sources = replace_keeping_lines(&re_refs, &sources, &mut ref_marker);
+    let sources = replace_keeping_lines(&re_noinclude, &sources, |_| String::new());
+    let sources = replace_keeping_lines(&re_default, &sources, |_| String::new());
+    let sources = replace_keeping_lines(&re_infobox, &sources, |_| String::new());
 
     let re_captures =
         Regex::new(r#"^\|\s*([A-Z][a-z]*)\s*=((?:\s*\(?[+-]?\d*\)?\??,?)*)$"#).unwrap();
-    let re_noise = Regex::new(r#"[^-?0-9]+"#).unwrap();
+    let re_markers = Regex::new(r#"\x00\d+\x00"#).unwrap();
+    // Refs follow the state they’re about:
+    let re_tokens = Regex::new(r#"\x00(\d+)\x00|([+-]?\d+)\)?(\?)?"#).unwrap();
 
     let mut result = HashMap::new();
 
-    for line in sources.lines() {
+    for (idx, line) in sources.lines().enumerate() {
+        let error = |what: &str| {
+            let line = re_markers.replace_all(line, "");
+            format!("line {}: {}: {}", idx + 1, what, line.trim())
+        };
         if !line.starts_with('|') {
             continue;
         }
@@ -172,26 +2343,39 @@ pub static OXIDATION_STATES: Lazy<HashMap<Symbol, OxidationStates>> = Lazy::new(
             ctx = Ctx::Predicted;
         } else {
             if ctx != Ctx::None {
-                let cpt = re_captures.captures(line).unwrap();
+                let without_refs = re_markers.replace_all(line, "");
+                let cpt = re_captures
+                    .captures(&without_refs)
+                    .ok_or_else(|| error("expected |Symbol=states"))?;
                 let symbol = Symbol(cpt.get(1).unwrap().as_str().to_string());
-                let states = cpt.get(2).unwrap().as_str();
-                let states = re_noise.replace_all(states, " ");
-                let states = states.trim();
-
-                let existing = result.entry(symbol).or_insert_with(|| OxidationStates {
-                    common: BTreeSet::new(),
-                    notable: BTreeSet::new(),
-                    predicted: BTreeSet::new(),
-                    citation_needed: BTreeSet::new(),
-                });
-
-                for state in states.split_whitespace() {
-                    if state.ends_with('?') {
-                        existing
-                            .citation_needed
-                            .insert(str::parse(&state[..state.len() - 1]).unwrap());
+                let states = line.split_once('=').unwrap().1;
+
+                let existing = result
+                    .entry(symbol)
+                    .or_insert_with(OxidationStates::default);
+
+                let mut last_state = None;
+                for token in re_tokens.captures_iter(states) {
+                    if let Some(marker) = token.get(1) {
+                        let id = &ref_ids[str::parse::<usize>(marker.as_str()).unwrap()];
+                        if let Some(state) = last_state {
+                            existing
+                                .sources
+                                .entry(state)
+                                .or_default()
+                                .insert(id.clone());
+                            if let Some(citation) = citations.get(id) {
+                                existing.citations.insert(id.clone(), citation.clone());
+                            }
+                        }
+                        continue;
+                    }
+                    let state = str::parse(token.get(2).unwrap().as_str())
+                        .map_err(|_| error("invalid oxidation state"))?;
+                    last_state = Some(state);
+                    if token.get(3).is_some() {
+                        existing.citation_needed.insert(state);
                     } else {
-                        let state = str::parse(state).unwrap();
                         let _ = match ctx {
                             Ctx::Common => existing.common.insert(state),
                             Ctx::Notable => existing.notable.insert(state),
@@ -201,26 +2385,48 @@ pub static OXIDATION_STATES: Lazy<HashMap<Symbol, OxidationStates>> = Lazy::new(
                     }
                 }
                 // There are duplicates in the data, e.g. for krypton, so let’s clean that up:
-                existing
-                    .notable
-                    .retain(|state| !existing.common.contains(state));
-                existing.predicted.retain(|state| {
-                    !existing.common.contains(state) && !existing.notable.contains(state)
-                });
-                existing.citation_needed.retain(|state| {
-                    !existing.common.contains(state)
-                        && !existing.notable.contains(state)
-                        && !existing.predicted.contains(state)
-                });
+                existing.normalize();
             }
         }
     }
 
+    Ok(result)
+}
+
+/// Like `Regex::replace_all`, but newlines from inside the matches are moved to the end of the
+/// line, so that line numbers stay the same for error messages.
+fn replace_keeping_lines(
+    re: &Regex,
+    text: &str,
+    mut replacement: impl FnMut(&str) -> String,
+) -> String {
+    fn push(result: &mut String, chunk: &str, pending_newlines: &mut usize) {
+        for (idx, part) in chunk.split('\n').enumerate() {
+            if idx > 0 {
+                result.push('\n');
+                result.extend(std::iter::repeat_n('\n', *pending_newlines));
+                *pending_newlines = 0;
+            }
+            result.push_str(part);
+        }
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut pending_newlines = 0;
+    let mut last = 0;
+    for m in re.find_iter(text) {
+        push(&mut result, &text[last..m.start()], &mut pending_newlines);
+        result.push_str(&replacement(m.as_str()));
+        pending_newlines += m.as_str().matches('\n').count();
+        last = m.end();
+    }
+    push(&mut result, &text[last..], &mut pending_newlines);
+    result.extend(std::iter::repeat_n('\n', pending_newlines));
     result
-});
+}
 
 /// Source: (accessed on 2024-11-10) <https://en.wikipedia.org/w/index.php?action=edit&title=Template%3AElement-symbol-to-oxidation-state-data&mfnoscript=1> via <https://en.wikipedia.org/wiki/Oxidation_state#List_of_oxidation_states_of_the_elements>.
-static OXIDATION_STATES_WIKIPEDIA: &'static str = r#"
+static OXIDATION_STATES_WIKIPEDIA: &str = r#"
 {{ {{{os-formatter|Element-symbol-to-oxidation-state-echo}}}
 |symbol={{{symbol|}}}
 |common={{#switch:{{{symbol|}}}
@@ -509,3 +2715,236 @@ static OXIDATION_STATES_WIKIPEDIA: &'static str = r#"
 
 --><noinclude>{{documentation}}</noinclude>
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_electron_configuration() {
+        let config = |z| ElectronConfiguration::ground_state(z);
+        assert_eq!(config(1).full(), "1s1");
+        assert_eq!(config(1).abbreviated(), "1s1");
+        assert_eq!(config(10).abbreviated(), "[He] 2s2 2p6");
+        assert_eq!(config(24).full(), "1s2 2s2 2p6 3s2 3p6 3d5 4s1");
+        assert_eq!(config(24).abbreviated(), "[Ar] 3d5 4s1");
+        assert_eq!(config(26).abbreviated(), "[Ar] 3d6 4s2");
+        assert_eq!(config(29).abbreviated(), "[Ar] 3d10 4s1");
+        assert_eq!(config(46).abbreviated(), "[Kr] 4d10");
+        assert_eq!(config(64).abbreviated(), "[Xe] 4f7 5d1 6s2");
+        assert_eq!(config(82).abbreviated(), "[Xe] 4f14 5d10 6s2 6p2");
+        assert_eq!(config(103).abbreviated(), "[Rn] 5f14 7s2 7p1");
+        assert_eq!(config(118).abbreviated(), "[Rn] 5f14 6d10 7s2 7p6");
+        for z in 1..=118 {
+            assert_eq!(config(z).electrons(), z);
+        }
+    }
+
+    #[test]
+    fn test_electron_configuration_occupancies() {
+        let cr = ElectronConfiguration::ground_state(24);
+        assert_eq!(cr.occupancy(2), 5);
+        assert_eq!(cr.occupancy(1), 6);
+        assert_eq!(cr.occupancy(3), 0);
+        assert_eq!(cr.valence_electrons(), 1);
+        assert_eq!(
+            ElectronConfiguration::ground_state(17).valence_electrons(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_electron_configuration_ions() {
+        let ion = |z, charge| ElectronConfiguration::ion(z, charge).unwrap().abbreviated();
+        assert_eq!(ion(26, 2), "[Ar] 3d6");
+        assert_eq!(ion(26, 3), "[Ar] 3d5");
+        assert_eq!(ion(11, 1), "[He] 2s2 2p6");
+        assert_eq!(ion(17, -1), "[Ne] 3s2 3p6");
+        assert_eq!(ion(8, -2), "[He] 2s2 2p6");
+        assert_eq!(ion(1, 0), "1s1");
+        assert!(ElectronConfiguration::ion(1, 2).is_err());
+    }
+
+    #[test]
+    fn test_electron_configuration_parse() {
+        for element in ALL.iter() {
+            let config = &element.electron_configuration;
+            assert_eq!(
+                ElectronConfiguration::parse(&config.full()).as_ref(),
+                Ok(config)
+            );
+        }
+        assert!(ElectronConfiguration::parse("1s3").is_err());
+        assert!(ElectronConfiguration::parse("1p1").is_err());
+        assert!(ElectronConfiguration::parse("1s1 1s1").is_err());
+    }
+
+    #[test]
+    fn test_parse_oxidation_states() {
+        let wikitext = "|common={{#switch:{{{symbol|}}}\n|H=<!-- a\nmultiline comment -->−1, +1\n|He=\n|Li=+1<ref>\nSource</ref>\n|Be=+2, x\n}}";
+        let error = parse_oxidation_states(wikitext).unwrap_err();
+        assert_eq!(error, "line 7: expected |Symbol=states: |Be=+2, x");
+        let wikitext = wikitext.replace(", x", "");
+        let states = parse_oxidation_states(&wikitext).unwrap();
+        let common = |symbol| &states[&Symbol::from(symbol)].common;
+        assert_eq!(common("H"), &BTreeSet::from([-1, 1]));
+        assert_eq!(common("Li"), &BTreeSet::from([1]));
+        assert!(common("He").is_empty());
+
+        let wikitext = "|common={{#switch:{{{symbol|}}}\n|N=-3,<ref name=a/> +5<ref name=\"a\">\nBook</ref><ref>Paper</ref>\n}}";
+        let n = &parse_oxidation_states(wikitext).unwrap()[&Symbol::from("N")];
+        assert_eq!(n.common, BTreeSet::from([-3, 5]));
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+        assert_eq!(n.sources[&-3], ids(&["a"]));
+        assert_eq!(n.sources[&5], ids(&["a", "ref-1"]));
+        assert_eq!(n.citations["a"], "Book");
+        assert_eq!(n.citations["ref-1"], "Paper");
+
+        // Not a ref:
+        let wikitext = "|common={{#switch:{{{symbol|}}}\n|Fe=+2, +3<ref>Book</ref> #99999\n}}";
+        let error = parse_oxidation_states(wikitext).unwrap_err();
+        assert_eq!(error, "line 2: expected |Symbol=states: |Fe=+2, +3 #99999");
+    }
+
+    #[test]
+    fn test_properties_with_units() {
+        let element = |z: usize| &ALL[z - 1];
+        let carbon = element(6);
+        assert_eq!(carbon.covalent_radius_pm, Some(76.0));
+        assert_eq!(carbon.van_der_waals_radius_pm, Some(170.0));
+        assert_eq!(carbon.ionization_energy.first_kj_per_mol, Some(1086.5));
+        assert_eq!(carbon.electron_affinity_kj_per_mol, Some(121.8));
+        assert_eq!(element(2).electron_affinity_kj_per_mol, None); // He
+        assert_eq!(element(26).van_der_waals_radius_pm, None); // Fe
+
+        // Helium has the highest ionization energy, caesium the lowest of the stable elements:
+        let energies = || (ALL.iter()).filter_map(|e| e.ionization_energy.first_kj_per_mol);
+        assert_eq!(energies().fold(f64::MIN, f64::max), 2372.3);
+        assert_eq!(element(55).ionization_energy.first_kj_per_mol, Some(375.7));
+        let json = serde_json::to_value(carbon).unwrap();
+        assert_eq!(json["covalent_radius_pm"], serde_json::json!(76.0));
+        assert_eq!(
+            json["ionization_energy"]["first_kj_per_mol"],
+            serde_json::json!(1086.5)
+        );
+    }
+
+    #[test]
+    fn test_atomic_weight_display() {
+        let mass = |z: usize| ALL[z - 1].atomic_weight.unwrap().to_string();
+        assert_eq!(mass(1), "1.0080");
+        assert_eq!(mass(10), "20.180");
+        assert_eq!(mass(32), "72.630");
+        assert_eq!(mass(52), "127.60");
+        assert_eq!(mass(43), "[98]");
+        let weight = AtomicWeight {
+            decimals: None,
+            ..AtomicWeight::standard(20.1797, 4)
+        };
+        assert_eq!(weight.to_string(), "20.1797");
+    }
+
+    #[test]
+    fn test_phase() {
+        let phase = |z: usize| ALL[z - 1].phase;
+        assert_eq!(phase(10), Phase::Gas); // Ne
+        assert_eq!(phase(35), Phase::Liquid); // Br
+        assert_eq!(phase(80), Phase::Liquid); // Hg
+        assert_eq!(phase(26), Phase::Solid); // Fe
+        assert_eq!(phase(100), Phase::Unknown); // Fm
+        assert_eq!(Phase::Liquid.to_string(), "liquid");
+        assert_eq!(serde_json::to_value(Phase::Gas).unwrap(), "gas");
+        for element in ALL.iter() {
+            if let (Some(melting), Some(boiling)) = (element.melting_point, element.boiling_point) {
+                assert!(melting <= boiling, "{}", element.symbol);
+            }
+        }
+    }
+
+    #[test]
+    fn test_discovery() {
+        let discovery = |z: usize| ALL[z - 1].discovery.clone().unwrap();
+        let iron = discovery(26);
+        assert!(iron.antiquity && iron.year < 0 && iron.discoverer.is_none());
+        let hydrogen = discovery(1);
+        assert_eq!(hydrogen.year, 1766);
+        assert!(!hydrogen.antiquity);
+        assert_eq!(hydrogen.discoverer.as_deref(), Some("Henry Cavendish"));
+        assert!(ALL.iter().all(|element| element.discovery.is_some()));
+        assert!(EXTENDED.iter().all(|element| element.discovery.is_none()));
+    }
+
+    #[test]
+    fn test_phase_at() {
+        let phase_at = |z: usize, t| ALL[z - 1].phase_at(t);
+        assert_eq!(phase_at(2, 0.0), Phase::Liquid); // He
+        assert_eq!(phase_at(6, 4000.0), Phase::Gas); // C sublimes
+        assert_eq!(phase_at(31, 310.0), Phase::Liquid); // Ga
+        assert_eq!(phase_at(80, 200.0), Phase::Solid); // Hg
+        assert_eq!(phase_at(85, 300.0), Phase::Unknown); // At
+        for element in ALL.iter().filter(|e| e.boiling_point.is_some()) {
+            assert_eq!(
+                element.phase_at(273.15),
+                element.phase,
+                "{}",
+                element.symbol
+            );
+        }
+    }
+
+    #[test]
+    fn test_isotopes() {
+        for element in ALL.iter() {
+            let abundances = element.isotopes.iter().filter_map(|i| i.abundance);
+            if abundances.clone().count() > 0 {
+                let sum: f64 = abundances.sum();
+                assert!((sum - 100.0).abs() < 0.01, "{}: {}", element.symbol, sum);
+            }
+        }
+        let radioactive: Vec<_> = ALL.iter().filter(|e| e.is_radioactive()).collect();
+        assert_eq!(radioactive.len(), 2 + (118 - 83 + 1));
+        assert_eq!(radioactive[2].symbol, Symbol::from("Bi"));
+        assert_eq!(ALL[0].isotopes[2].half_life, Some(12.32 * 365.25 * 86400.0));
+        assert_eq!(ALL[0].isotopes[2].decay_mode, Some(DecayMode::BetaMinus));
+    }
+
+    #[test]
+    fn test_extended() {
+        let names = |z: usize| {
+            let element = &EXTENDED[z - 119];
+            (element.symbol.to_string(), element.name.as_str())
+        };
+        assert_eq!(names(119), ("Uue".to_string(), "Ununennium"));
+        assert_eq!(names(120), ("Ubn".to_string(), "Unbinilium"));
+        assert_eq!(names(122), ("Ubb".to_string(), "Unbibium"));
+        assert_eq!(names(139), ("Ute".to_string(), "Untriennium"));
+        assert_eq!(names(168), ("Uho".to_string(), "Unhexoctium"));
+        let block = |block| EXTENDED.iter().filter(|e| e.block == block).count();
+        assert_eq!(
+            (block(0), block(4), block(3), block(2), block(1)),
+            (2, 18, 14, 10, 6)
+        );
+        assert_eq!(EXTENDED[153 - 119].group, Some(3));
+        assert_eq!(
+            EXTENDED[0].oxidation_states.predicted,
+            BTreeSet::from([1, 3, 5])
+        );
+    }
+
+    #[test]
+    fn test_categories() {
+        let count = |category| ALL.iter().filter(|e| e.category == category).count();
+        assert_eq!(count(Category::AlkaliMetal), 6);
+        assert_eq!(count(Category::AlkalineEarthMetal), 6);
+        assert_eq!(count(Category::Lanthanide), 15);
+        assert_eq!(count(Category::Actinide), 15);
+        assert_eq!(count(Category::PostTransitionMetal), 8);
+        assert_eq!(count(Category::Halogen), 5);
+        assert_eq!(count(Category::Unknown), 10);
+        assert_eq!(ALL[29].category, Category::TransitionMetal); // Zn
+        assert_eq!(
+            serde_json::to_string(&Category::NobleGas).unwrap(),
+            format!("\"{}\"", Category::NobleGas)
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::elements::{Category, Symbol};
+use clap::ValueEnum;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Lang {
+    /// English
+    En,
+    /// Polish
+    Pl,
+}
+
+pub fn element_name(symbol: &Symbol, lang: Lang) -> Option<&'static str> {
+    ELEMENT_NAMES.get(symbol).map(|&(en, pl)| match lang {
+        Lang::En => en,
+        Lang::Pl => pl,
+    })
+}
+
+/// Plural, capitalized, for legends.
+pub fn category_name(category: Category, lang: Lang) -> &'static str {
+    match (lang, category) {
+        (Lang::En, Category::AlkaliMetal) => "Alkali metals",
+        (Lang::En, Category::AlkalineEarthMetal) => "Alkaline earth metals",
+        (Lang::En, Category::Lanthanide) => "Lanthanides",
+        (Lang::En, Category::Actinide) => "Actinides",
+        (Lang::En, Category::TransitionMetal) => "Transition metals",
+        (Lang::En, Category::PostTransitionMetal) => "Post-transition metals",
+        (Lang::En, Category::Metalloid) => "Metalloids",
+        (Lang::En, Category::ReactiveNonmetal) => "Reactive nonmetals",
+        (Lang::En, Category::Halogen) => "Halogens",
+        (Lang::En, Category::NobleGas) => "Noble gases",
+        (Lang::En, Category::Unknown) => "Unknown properties",
+        (Lang::Pl, Category::AlkaliMetal) => "Metale alkaliczne",
+        (Lang::Pl, Category::AlkalineEarthMetal) => "Metale ziem alkalicznych",
+        (Lang::Pl, Category::Lanthanide) => "Lantanowce",
+        (Lang::Pl, Category::Actinide) => "Aktynowce",
+        (Lang::Pl, Category::TransitionMetal) => "Metale przejściowe",
+        (Lang::Pl, Category::PostTransitionMetal) => "Metale bloku p",
+        (Lang::Pl, Category::Metalloid) => "Półmetale",
+        (Lang::Pl, Category::ReactiveNonmetal) => "Niemetale",
+        (Lang::Pl, Category::Halogen) => "Fluorowce",
+        (Lang::Pl, Category::NobleGas) => "Gazy szlachetne",
+        (Lang::Pl, Category::Unknown) => "Właściwości nieznane",
+    }
+}
+
+static ELEMENT_NAMES: Lazy<HashMap<Symbol, (&str, &str)>> = Lazy::new(|| {
+    ELEMENT_NAMES_TABLE
+        .iter()
+        .map(|&(symbol, en, pl)| (Symbol::from(symbol), (en, pl)))
+        .collect()
+});
+
+/// English names follow the IUPAC spelling (“aluminium”, “caesium”, “sulfur”), Polish ones the
+/// PTChem recommendations. Both are capitalized, as they’re mostly used as labels.
+static ELEMENT_NAMES_TABLE: &[(&str, &str, &str)] = &[
+    ("H", "Hydrogen", "Wodór"),
+    ("He", "Helium", "Hel"),
+    ("Li", "Lithium", "Lit"),
+    ("Be", "Beryllium", "Beryl"),
+    ("B", "Boron", "Bor"),
+    ("C", "Carbon", "Węgiel"),
+    ("N", "Nitrogen", "Azot"),
+    ("O", "Oxygen", "Tlen"),
+    ("F", "Fluorine", "Fluor"),
+    ("Ne", "Neon", "Neon"),
+    ("Na", "Sodium", "Sód"),
+    ("Mg", "Magnesium", "Magnez"),
+    ("Al", "Aluminium", "Glin"),
+    ("Si", "Silicon", "Krzem"),
+    ("P", "Phosphorus", "Fosfor"),
+    ("S", "Sulfur", "Siarka"),
+    ("Cl", "Chlorine", "Chlor"),
+    ("Ar", "Argon", "Argon"),
+    ("K", "Potassium", "Potas"),
+    ("Ca", "Calcium", "Wapń"),
+    ("Sc", "Scandium", "Skand"),
+    ("Ti", "Titanium", "Tytan"),
+    ("V", "Vanadium", "Wanad"),
+    ("Cr", "Chromium", "Chrom"),
+    ("Mn", "Manganese", "Mangan"),
+    ("Fe", "Iron", "Żelazo"),
+    ("Co", "Cobalt", "Kobalt"),
+    ("Ni", "Nickel", "Nikiel"),
+    ("Cu", "Copper", "Miedź"),
+    ("Zn", "Zinc", "Cynk"),
+    ("Ga", "Gallium", "Gal"),
+    ("Ge", "Germanium", "German"),
+    ("As", "Arsenic", "Arsen"),
+    ("Se", "Selenium", "Selen"),
+    ("Br", "Bromine", "Brom"),
+    ("Kr", "Krypton", "Krypton"),
+    ("Rb", "Rubidium", "Rubid"),
+    ("Sr", "Strontium", "Stront"),
+    ("Y", "Yttrium", "Itr"),
+    ("Zr", "Zirconium", "Cyrkon"),
+    ("Nb", "Niobium", "Niob"),
+    ("Mo", "Molybdenum", "Molibden"),
+    ("Tc", "Technetium", "Technet"),
+    ("Ru", "Ruthenium", "Ruten"),
+    ("Rh", "Rhodium", "Rod"),
+    ("Pd", "Palladium", "Pallad"),
+    ("Ag", "Silver", "Srebro"),
+    ("Cd", "Cadmium", "Kadm"),
+    ("In", "Indium", "Ind"),
+    ("Sn", "Tin", "Cyna"),
+    ("Sb", "Antimony", "Antymon"),
+    ("Te", "Tellurium", "Tellur"),
+    ("I", "Iodine", "Jod"),
+    ("Xe", "Xenon", "Ksenon"),
+    ("Cs", "Caesium", "Cez"),
+    ("Ba", "Barium", "Bar"),
+    ("La", "Lanthanum", "Lantan"),
+    ("Ce", "Cerium", "Cer"),
+    ("Pr", "Praseodymium", "Prazeodym"),
+    ("Nd", "Neodymium", "Neodym"),
+    ("Pm", "Promethium", "Promet"),
+    ("Sm", "Samarium", "Samar"),
+    ("Eu", "Europium", "Europ"),
+    ("Gd", "Gadolinium", "Gadolin"),
+    ("Tb", "Terbium", "Terb"),
+    ("Dy", "Dysprosium", "Dysproz"),
+    ("Ho", "Holmium", "Holm"),
+    ("Er", "Erbium", "Erb"),
+    ("Tm", "Thulium", "Tul"),
+    ("Yb", "Ytterbium", "Iterb"),
+    ("Lu", "Lutetium", "Lutet"),
+    ("Hf", "Hafnium", "Hafn"),
+    ("Ta", "Tantalum", "Tantal"),
+    ("W", "Tungsten", "Wolfram"),
+    ("Re", "Rhenium", "Ren"),
+    ("Os", "Osmium", "Osm"),
+    ("Ir", "Iridium", "Iryd"),
+    ("Pt", "Platinum", "Platyna"),
+    ("Au", "Gold", "Złoto"),
+    ("Hg", "Mercury", "Rtęć"),
+    ("Tl", "Thallium", "Tal"),
+    ("Pb", "Lead", "Ołów"),
+    ("Bi", "Bismuth", "Bizmut"),
+    ("Po", "Polonium", "Polon"),
+    ("At", "Astatine", "Astat"),
+    ("Rn", "Radon", "Radon"),
+    ("Fr", "Francium", "Frans"),
+    ("Ra", "Radium", "Rad"),
+    ("Ac", "Actinium", "Aktyn"),
+    ("Th", "Thorium", "Tor"),
+    ("Pa", "Protactinium", "Protaktyn"),
+    ("U", "Uranium", "Uran"),
+    ("Np", "Neptunium", "Neptun"),
+    ("Pu", "Plutonium", "Pluton"),
+    ("Am", "Americium", "Ameryk"),
+    ("Cm", "Curium", "Kiur"),
+    ("Bk", "Berkelium", "Berkel"),
+    ("Cf", "Californium", "Kaliforn"),
+    ("Es", "Einsteinium", "Einstein"),
+    ("Fm", "Fermium", "Ferm"),
+    ("Md", "Mendelevium", "Mendelew"),
+    ("No", "Nobelium", "Nobel"),
+    ("Lr", "Lawrencium", "Lorens"),
+    ("Rf", "Rutherfordium", "Rutherford"),
+    ("Db", "Dubnium", "Dubn"),
+    ("Sg", "Seaborgium", "Seaborg"),
+    ("Bh", "Bohrium", "Bohr"),
+    ("Hs", "Hassium", "Has"),
+    ("Mt", "Meitnerium", "Meitner"),
+    ("Ds", "Darmstadtium", "Darmsztadt"),
+    ("Rg", "Roentgenium", "Roentgen"),
+    ("Cn", "Copernicium", "Kopernik"),
+    ("Nh", "Nihonium", "Nihon"),
+    ("Fl", "Flerovium", "Flerow"),
+    ("Mc", "Moscovium", "Moskow"),
+    ("Lv", "Livermorium", "Liwermor"),
+    ("Ts", "Tennessine", "Tenes"),
+    ("Og", "Oganesson", "Oganeson"),
+];
@@ -2,7 +2,13 @@ use std::collections::HashMap;
 use std::fmt::Write;
 
 mod cli;
+mod color;
+mod data;
 mod elements;
+mod l10n;
+mod nuclides;
+mod orbitals;
+mod overrides;
 mod query;
 
 fn main() {
@@ -17,39 +23,114 @@ fn main() {
 
 fn main_result() -> Result<(), String> {
     let args = cli::Args::parse();
-    if let Some(dump_query) = args.dump {
-        let mut elements = vec![];
-        for element in elements::ALL.iter() {
+    let elements = prepare_elements(&args)?;
+    if let Some(path) = &args.diff_ox_template {
+        let error = |err: String| format!("Error loading {}: {}", path.display(), err);
+        let wikitext = std::fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+        let newer = elements::parse_oxidation_states(&wikitext).map_err(error)?;
+        for (oxidation_override, was) in overrides::diff(&elements::OXIDATION_STATES, &newer) {
+            let was: Vec<String> = was.iter().map(|state| state.to_string()).collect();
+            println!("{}  # was {{{}}}", oxidation_override, was.join(", "));
+        }
+        Ok(())
+    } else if let Some(dump_query) = &args.dump {
+        let mut dumped = vec![];
+        for element in elements.iter() {
             if dump_query.evaluate_on(element)? {
-                elements.push(element);
+                dumped.push(element);
             }
         }
-        let json = serde_json::to_string_pretty(&elements).map_err(|err| err.to_string())?;
+        let json = serde_json::to_string_pretty(&dumped).map_err(|err| err.to_string())?;
         println!("{}", json);
         Ok(())
+    } else if let Some(atomic_number) = args.orbitals {
+        let element = elements
+            .iter()
+            .find(|element| element.atomic_number == atomic_number)
+            .ok_or(format!("Unknown atomic number: {}", atomic_number))?;
+        println!("{}", orbitals::generate_svg(element, &args)?);
+        Ok(())
+    } else if args.nuclides {
+        println!("{}", nuclides::generate_svg(&elements, &args)?);
+        Ok(())
     } else {
-        let tiles = make_tiles(&args);
-        let (tiles, colors) = calculate_colors(&tiles, &args)?;
-        println!("{}", generate_svg(&tiles, &colors, &args));
+        let tiles = make_tiles(&elements, &args);
+        let (tiles, colors, legends) = calculate_colors(&tiles, &args)?;
+        println!("{}", generate_svg(&tiles, &colors, &legends, &args));
         Ok(())
     }
 }
 
+/// Element data as seen by queries and renderers, i.e. after applying CLI options.
+fn prepare_elements(args: &cli::Args) -> Result<Vec<elements::Element>, String> {
+    let mut elements = elements::ALL.clone();
+    if args.extended {
+        elements.extend(elements::EXTENDED.iter().cloned());
+    }
+    for path in args.data.iter() {
+        data::overlay(&mut elements, path)?;
+    }
+    for oxidation_override in args.oxidation_overrides() {
+        oxidation_override.apply(&mut elements)?;
+    }
+    for element in elements.iter_mut() {
+        // Names from --data files take precedence:
+        let english = l10n::element_name(&element.symbol, l10n::Lang::En);
+        if element.local_name.is_none() && english == Some(element.name.as_str()) {
+            element.local_name = l10n::element_name(&element.symbol, args.lang).map(String::from);
+        }
+        element.phase_at_t = match args.temperature {
+            Some(temperature) => element.phase_at(temperature),
+            None => element.phase,
+        };
+    }
+    Ok(elements)
+}
+
 #[derive(Debug, Clone)]
 struct Tile {
     element: elements::Element,
     graphical_x: u8,
     graphical_y: u8,
     marks: Vec<String>,
+    /// Background for continuous colorings, overridden by marks.
+    fill: Option<String>,
+    /// Whether texts need to be light to be readable on `fill`.
+    dark: bool,
+    /// Not yet discovered `--as-of` the given year.
+    undiscovered: bool,
 }
 
-fn make_tiles(args: &cli::Args) -> Vec<Tile> {
-    elements::ALL
+impl Tile {
+    /// Hidden tiles still take their place, but don’t take part in colorings.
+    fn is_hidden(&self, args: &cli::Args) -> bool {
+        self.undiscovered && args.undiscovered == cli::Undiscovered::Hide
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Legend {
+    Gradient {
+        title: String,
+        min: f64,
+        max: f64,
+        scale: color::ColorScale,
+        has_no_data: bool,
+    },
+    Categorical {
+        entries: Vec<(String /* label */, String /* color */)>,
+    },
+}
+
+fn make_tiles(elements: &[elements::Element], args: &cli::Args) -> Vec<Tile> {
+    elements
         .iter()
         .map(|element| {
             let period = element.period;
             let group = element.group;
             let atomic_number = element.atomic_number;
+            let g_block = element.block == 4;
+            let (f_rows, g_columns) = f_rows_and_g_columns(args);
 
             let (graphical_y, graphical_x) = if !args.wide {
                 match (period, group) {
@@ -60,8 +141,10 @@ fn make_tiles(args: &cli::Args) -> Vec<Tile> {
                             (1, 18)
                         }
                     }
-                    (6, None | Some(3)) => (period + 3, 4 + atomic_number - 57),
-                    (7, None | Some(3)) => (period + 3, 4 + atomic_number - 89),
+                    (6, None | Some(3)) => (period + f_rows, 4 + atomic_number - 57),
+                    (7, None | Some(3)) => (period + f_rows, 4 + atomic_number - 89),
+                    (8, None) if g_block => (period + f_rows, 1 + atomic_number - 121),
+                    (8, None | Some(3)) => (period + f_rows + 1, 4 + atomic_number - 139),
                     (p, Some(g)) => (p, g),
                     _ => (0, 0),
                 }
@@ -71,13 +154,15 @@ fn make_tiles(args: &cli::Args) -> Vec<Tile> {
                         if args.helium_in_2 {
                             (1, 2)
                         } else {
-                            (1, 32)
+                            (1, 32 + g_columns)
                         }
                     }
                     (p, Some(g)) if g <= 2 => (p, g),
-                    (p, Some(g)) => (p, g + 14),
-                    (6, None) => (period, 3 + atomic_number - 57),
-                    (7, None) => (period, 3 + atomic_number - 89),
+                    (p, Some(g)) => (p, g + 14 + g_columns),
+                    (6, None) => (period, 3 + g_columns + atomic_number - 57),
+                    (7, None) => (period, 3 + g_columns + atomic_number - 89),
+                    (8, None) if g_block => (period, 3 + atomic_number - 121),
+                    (8, None) => (period, 3 + g_columns + atomic_number - 139),
                     _ => (0, 0),
                 }
             };
@@ -87,54 +172,155 @@ fn make_tiles(args: &cli::Args) -> Vec<Tile> {
                 graphical_x,
                 graphical_y,
                 marks: vec![],
+                fill: None,
+                dark: false,
+                undiscovered: args.as_of.is_some_and(|year| {
+                    (element.discovery.as_ref()).is_none_or(|discovery| discovery.year > year)
+                }),
             }
         })
         .collect()
 }
 
-fn calculate_colors(
-    tiles: &Vec<Tile>,
-    args: &cli::Args,
-) -> Result<
-    (
-        Vec<Tile>,
-        HashMap<String /* class name */, String /* color */>,
-    ),
-    String,
-> {
-    fn ith_class(i: usize) -> String {
-        format!("mark-{}", i)
-    }
+type Colors = HashMap<String /* class name */, String /* color */>;
 
-    // FIXME: use newtype for color and class
+/// How far below their periods the f-block rows are drawn, and how many columns the g-block
+/// takes in `--wide`, both depending on whether period 8 is drawn with `--extended`.
+fn f_rows_and_g_columns(args: &cli::Args) -> (u8, u8) {
+    if args.extended {
+        (4, 18)
+    } else {
+        (3, 0)
+    }
+}
 
-    let colors: HashMap<String /* class */, String /* color */> = args
-        .mark
-        .iter()
-        .enumerate()
-        .map(|(i, mark)| (ith_class(i), mark.color.clone()))
-        .collect();
+fn calculate_colors(
+    tiles: &[Tile],
+    args: &cli::Args,
+) -> Result<(Vec<Tile>, Colors, Vec<Legend>), String> {
+    let colors = mark_colors(args);
 
-    let tiles: Vec<Tile> = tiles
+    let mut tiles: Vec<Tile> = tiles
         .iter()
         .map(|tile| {
             let mut tile = tile.clone();
-            let el = &tile.element;
-
-            for (i, mrk) in args.mark.iter().enumerate() {
-                if mrk.query.evaluate_on(el)? {
-                    tile.marks.push(ith_class(i));
-                }
-            }
-
+            tile.marks = mark_classes(&tile.element, args)?;
             Ok(tile)
         })
         .collect::<Result<Vec<_>, String>>()?;
 
-    Ok((tiles, colors))
+    let mut legends = vec![];
+
+    if let Some(gradient) = &args.gradient {
+        let values = tiles
+            .iter()
+            .map(|tile| {
+                if tile.is_hidden(args) {
+                    Ok(None)
+                } else {
+                    gradient.query.evaluate_number_on(&tile.element)
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let known = values.iter().flatten();
+        let min = known.clone().cloned().fold(f64::INFINITY, f64::min);
+        let max = known.cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        for (tile, value) in tiles.iter_mut().zip(values.iter()) {
+            tile.fill = Some(match value {
+                Some(value) => {
+                    let color = gradient.scale.color(*value, min, max);
+                    tile.dark = color.is_dark() && tile.marks.is_empty();
+                    color.to_string()
+                }
+                None => "url(#no-data)".to_string(),
+            });
+        }
+
+        if min <= max {
+            let (min, max) = gradient.scale.domain(min, max);
+            legends.push(Legend::Gradient {
+                title: gradient.query.to_string(),
+                min,
+                max,
+                scale: gradient.scale.clone(),
+                has_no_data: values
+                    .iter()
+                    .zip(tiles.iter())
+                    .any(|(v, tile)| v.is_none() && !tile.is_hidden(args)),
+            });
+        }
+    }
+
+    if let Some(cli::ColorBy::Category) = args.color_by {
+        for tile in tiles.iter_mut() {
+            tile.fill = Some(category_color(tile.element.category).to_string());
+        }
+        legends.push(Legend::Categorical {
+            entries: elements::Category::ALL
+                .iter()
+                .filter(|&&category| {
+                    tiles
+                        .iter()
+                        .any(|tile| tile.element.category == category && !tile.is_hidden(args))
+                })
+                .map(|&category| {
+                    (
+                        l10n::category_name(category, args.lang).to_string(),
+                        category_color(category).to_string(),
+                    )
+                })
+                .collect(),
+        });
+    }
+
+    Ok((tiles, colors, legends))
+}
+
+fn ith_class(i: usize) -> String {
+    format!("mark-{}", i)
+}
+
+// FIXME: use newtype for color and class
+
+fn mark_colors(args: &cli::Args) -> Colors {
+    args.mark
+        .iter()
+        .enumerate()
+        .map(|(i, mark)| (ith_class(i), mark.color.clone()))
+        .collect()
+}
+
+/// Classes of all `--mark` queries matching the subject.
+fn mark_classes(subject: &dyn query::Subject, args: &cli::Args) -> Result<Vec<String>, String> {
+    let mut marks = vec![];
+    for (i, mrk) in args.mark.iter().enumerate() {
+        if mrk.query.evaluate_on(subject)? {
+            marks.push(ith_class(i));
+        }
+    }
+    Ok(marks)
+}
+
+/// Light enough for black texts.
+fn category_color(category: elements::Category) -> &'static str {
+    use elements::Category::*;
+    match category {
+        AlkaliMetal => "#ff6666",
+        AlkalineEarthMetal => "#ffdead",
+        Lanthanide => "#ffbfff",
+        Actinide => "#ff99cc",
+        TransitionMetal => "#ffc0c0",
+        PostTransitionMetal => "#cccccc",
+        Metalloid => "#cccc99",
+        ReactiveNonmetal => "#a0ffa0",
+        Halogen => "#ffff99",
+        NobleGas => "#c0ffff",
+        Unknown => "#e8e8e8",
+    }
 }
 
-fn generate_svg(tiles: &Vec<Tile>, colors: &HashMap<String, String>, args: &cli::Args) -> String {
+fn generate_svg(tiles: &[Tile], colors: &Colors, legends: &[Legend], args: &cli::Args) -> String {
     let width: u32 = 50;
     let stroke_width: u32 = 1;
 
@@ -162,72 +348,65 @@ fn generate_svg(tiles: &Vec<Tile>, colors: &HashMap<String, String>, args: &cli:
         r#"
   <desc>
     Created with https://github.com/michalrus/periodic-table-generator
-    ❯ periodic-table-generator {}
+    ❯ periodic-table-generator {}{}
   </desc>
   <style>
     .elements text.Z {{ font-size: {}px; text-anchor: start; alignment-baseline: before-edge; }}
     .elements text:not(.Z) {{ font-size: {}px; text-anchor: middle; alignment-baseline: middle; }}
-    .elements rect {{ stroke-width: {}; height: {}px; }}
-    .elements rect:not([width]) {{ stroke: black; width: {}px; }}
-    .elements rect:not([fill]) {{ fill: white; }}
+    .elements text.mass, .elements text.name {{ font-size: {}px; }}
+    .elements g.dark text {{ fill: white; }}
+    .elements g.undiscovered {{ opacity: 0.25; }}
+    .elements text.gas {{ fill: none !important; stroke: black; stroke-width: {}; }}
+    .elements g.dark text.gas {{ stroke: white; }}
+    .elements text.liquid {{ font-style: italic; }}
+    .legend text {{ font-size: {}px; alignment-baseline: middle; }}
+    .legend rect {{ stroke: black; stroke-width: {}; }}
     .group-numbers text, .period-numbers text {{ font-size: {}px; fill: #808080; text-anchor: middle; alignment-baseline: middle; }}"#,
-        cli::escaped_argv().replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;"),
+        escape_xml(&cli::escaped_argv()),
+        escape_xml(&cli::override_notes(args)),
         width / 4,
         width / 2,
+        width * 7 / 50,
+        width as f64 / 50.0,
+        width * 6 / 25,
         stroke_width,
-        width,
-        width,
         width * 3/8,
     )
     .unwrap();
 
-    {
-        let mut colors_sorted = colors.into_iter().collect::<Vec<_>>();
-        colors_sorted.sort();
-        for (mark, color) in colors_sorted {
-            writeln!(svg, r#"    .{} {{ fill: {} !important; }}"#, mark, color).unwrap();
-        }
-    }
+    write_tile_styles(&mut svg, "elements", width, stroke_width, colors);
 
     svg.push_str("  </style>\n");
+
+    write_defs(&mut svg, legends, width);
+
     writeln!(svg, r#"  <g class="elements">"#).unwrap();
 
-    for tile in tiles.iter() {
+    for tile in tiles.iter().filter(|tile| !tile.is_hidden(args)) {
         let element = &tile.element;
         let x = tile.graphical_x as u32 * width;
         let y = tile.graphical_y as u32 * width;
 
-        write!(svg, "    ").unwrap();
-
-        if tile.marks.len() <= 1 {
-            write!(
-                svg,
-                r#"<rect x="{}" y="{}"{}/>"#,
-                x,
-                y,
-                if !tile.marks.is_empty() {
-                    format!(" class=\"{}\"", tile.marks.join(" "))
-                } else {
-                    String::new()
-                }
-            )
-            .unwrap();
-        } else {
-            let num_marks = tile.marks.len();
-            let stripe_width: f64 = width as f64 / num_marks as f64;
-            for (i, mark) in tile.marks.iter().enumerate() {
-                write!(
-                    svg,
-                    r#"<rect x="{:.5}" y="{}" width="{:.5}" class="{}"/>"#,
-                    x as f64 + i as f64 * stripe_width,
-                    y,
-                    stripe_width,
-                    mark,
-                )
-                .unwrap();
-            }
-            write!(svg, r#"<rect fill="none" x="{}" y="{}"/>"#, x, y,).unwrap();
+        let mut classes = vec![];
+        if tile.dark {
+            classes.push("dark");
+        }
+        if tile.undiscovered {
+            classes.push("undiscovered");
         }
+        write!(
+            svg,
+            "    <g{}><title>{}</title>",
+            if classes.is_empty() {
+                String::new()
+            } else {
+                format!(r#" class="{}""#, classes.join(" "))
+            },
+            escape_xml(element.display_name())
+        )
+        .unwrap();
+
+        write_tile_rects(&mut svg, x, y, width, tile.fill.as_deref(), &tile.marks);
 
         if !args.no_z {
             let text_x = x + (3 * width / 50);
@@ -240,33 +419,83 @@ fn generate_svg(tiles: &Vec<Tile>, colors: &HashMap<String, String>, args: &cli:
             .unwrap();
         }
 
-        if args.no_symbols {
-            writeln!(svg).unwrap();
-        } else {
+        // Small labels under the symbol, from top to bottom:
+        let mut labels: Vec<(&str, String)> = vec![];
+        if args.mass {
+            let mass = element.atomic_weight.map(|mass| mass.to_string());
+            labels.push(("mass", mass.unwrap_or_default()));
+        }
+        if args.names {
+            labels.push(("name", element.display_name().to_string()));
+        }
+        let num_labels = labels.len() as u32;
+
+        if !args.no_symbols {
+            let text_x = x + width / 2;
+            let text_y = y + width / 2 + (3 * width / 50) - num_labels * (3 * width / 50);
+            let phase_class = match element.phase_at_t {
+                elements::Phase::Gas | elements::Phase::Liquid if args.phase_style => {
+                    format!(r#" class="{}""#, element.phase_at_t)
+                }
+                _ => String::new(),
+            };
+            write!(
+                svg,
+                r#"<text x="{}" y="{}"{}>{}</text>"#,
+                text_x, text_y, phase_class, element.symbol
+            )
+            .unwrap();
+        }
+
+        for (i, (class, label)) in labels.iter().enumerate() {
             let text_x = x + width / 2;
-            let text_y = y + width / 2 + (3 * width / 50);
-            writeln!(
+            let text_y = y + width * (46 - 5 * num_labels + 8 * i as u32) / 50;
+            // Squeeze long labels (e.g. “Rutherfordium”) to fit within the tile:
+            let text_length = if label.chars().count() > 10 {
+                format!(
+                    r#" textLength="{}" lengthAdjust="spacingAndGlyphs""#,
+                    width * 46 / 50
+                )
+            } else {
+                String::new()
+            };
+            write!(
                 svg,
-                r#"<text x="{}" y="{}">{}</text>"#,
+                r#"<text x="{}" y="{}" class="{}"{}>{}</text>"#,
                 text_x,
                 text_y,
-                element.symbol.to_string()
+                class,
+                text_length,
+                escape_xml(label)
             )
             .unwrap();
         }
+
+        writeln!(svg, "</g>").unwrap();
     }
 
     svg.push_str("  </g>\n");
 
+    write_legends(&mut svg, legends, width);
+
+    let (f_rows, g_columns) = f_rows_and_g_columns(args);
+    let (f_rows, g_columns) = (f_rows as u32, g_columns as u32);
+
     if !args.no_group_numbers {
         write!(svg, r#"  <g class="group-numbers">"#).unwrap();
 
-        let locations = (1..=7).flat_map(|group| match group {
+        let last = if args.extended { 8 } else { 7 };
+        let locations = (1..=last).flat_map(|group| match group {
             6..=7 if !args.wide => vec![
-                (group, 0 * width, group * width),
-                (group, 3 * width, (group + 3) * width),
+                (group, 0, group * width),
+                (group, 3 * width, (group + f_rows) * width),
             ],
-            _ => vec![(group, 0 * width, group * width)],
+            8 if !args.wide => vec![
+                (group, 0, group * width),
+                (group, 0, (group + f_rows) * width),
+                (group, 3 * width, (group + f_rows + 1) * width),
+            ],
+            _ => vec![(group, 0, group * width)],
         });
 
         for (group, x, y) in locations {
@@ -286,8 +515,11 @@ fn generate_svg(tiles: &Vec<Tile>, colors: &HashMap<String, String>, args: &cli:
         write!(svg, r#"  <g class="period-numbers">"#).unwrap();
 
         let locations = (1..=18).flat_map(|period| match period {
-            3 if !args.wide => vec![(period, period * width, 0), (period, 18 * width, 8 * width)],
-            3.. if args.wide => vec![(period, (period + 14) * width, 0)],
+            3 if !args.wide => vec![
+                (period, period * width, 0),
+                (period, 18 * width, (5 + f_rows) * width),
+            ],
+            3.. if args.wide => vec![(period, (period + 14 + g_columns) * width, 0)],
             _ => vec![(period, period * width, 0)],
         });
 
@@ -308,3 +540,299 @@ fn generate_svg(tiles: &Vec<Tile>, colors: &HashMap<String, String>, args: &cli:
 
     svg
 }
+
+/// Rules for tiles drawn with `write_tile_rects` inside `<g class="{group_class}">`.
+fn write_tile_styles(
+    svg: &mut String,
+    group_class: &str,
+    width: u32,
+    stroke_width: u32,
+    colors: &Colors,
+) {
+    writeln!(
+        svg,
+        r#"    .{g} rect {{ stroke-width: {s}; height: {w}px; }}
+    .{g} rect:not([width]) {{ stroke: black; width: {w}px; }}
+    .{g} rect:not([fill]) {{ fill: white; }}"#,
+        g = group_class,
+        s = stroke_width,
+        w = width,
+    )
+    .unwrap();
+
+    let mut colors_sorted = colors.iter().collect::<Vec<_>>();
+    colors_sorted.sort();
+    for (mark, color) in colors_sorted {
+        writeln!(svg, r#"    .{} {{ fill: {} !important; }}"#, mark, color).unwrap();
+    }
+}
+
+/// A tile’s background: a single rect, or vertical stripes for multiple marks.
+fn write_tile_rects(
+    svg: &mut String,
+    x: u32,
+    y: u32,
+    width: u32,
+    fill: Option<&str>,
+    marks: &[String],
+) {
+    if marks.len() <= 1 {
+        write!(
+            svg,
+            r#"<rect x="{}" y="{}"{}{}/>"#,
+            x,
+            y,
+            if let Some(fill) = fill {
+                format!(" fill=\"{}\"", fill)
+            } else {
+                String::new()
+            },
+            if !marks.is_empty() {
+                format!(" class=\"{}\"", marks.join(" "))
+            } else {
+                String::new()
+            }
+        )
+        .unwrap();
+    } else {
+        let num_marks = marks.len();
+        let stripe_width: f64 = width as f64 / num_marks as f64;
+        for (i, mark) in marks.iter().enumerate() {
+            write!(
+                svg,
+                r#"<rect x="{:.5}" y="{}" width="{:.5}" class="{}"/>"#,
+                x as f64 + i as f64 * stripe_width,
+                y,
+                stripe_width,
+                mark,
+            )
+            .unwrap();
+        }
+        write!(svg, r#"<rect fill="none" x="{}" y="{}"/>"#, x, y,).unwrap();
+    }
+}
+
+fn write_defs(svg: &mut String, legends: &[Legend], width: u32) {
+    svg.push_str("  <defs>\n");
+    writeln!(
+        svg,
+        r##"    <pattern id="no-data" patternUnits="userSpaceOnUse" width="{w}" height="{w}" patternTransform="rotate(45)"><rect width="{w}" height="{w}" fill="white"/><line x1="0" y1="0" x2="0" y2="{w}" stroke="#c0c0c0" stroke-width="{s}"/></pattern>"##,
+        w = width / 10,
+        s = width / 20,
+    )
+    .unwrap();
+    for (i, legend) in legends.iter().enumerate() {
+        match legend {
+            Legend::Gradient { scale, .. } => {
+                let stops = &scale.stops;
+                write!(svg, r#"    <linearGradient id="legend-{}">"#, i).unwrap();
+                for (j, stop) in stops.iter().enumerate() {
+                    write!(
+                        svg,
+                        r#"<stop offset="{:.5}" stop-color="{}"/>"#,
+                        j as f64 / (stops.len() - 1) as f64,
+                        stop
+                    )
+                    .unwrap();
+                }
+                writeln!(svg, "</linearGradient>").unwrap();
+            }
+            Legend::Categorical { .. } => {}
+        }
+    }
+    svg.push_str("  </defs>\n");
+}
+
+/// Legends are drawn in the empty space above the transition metals.
+fn write_legends(svg: &mut String, legends: &[Legend], width: u32) {
+    if legends.is_empty() {
+        return;
+    }
+
+    writeln!(svg, r#"  <g class="legend">"#).unwrap();
+    let x = 4 * width;
+    let mut y = width + width / 5;
+    for (i, legend) in legends.iter().enumerate() {
+        match legend {
+            Legend::Gradient {
+                title,
+                min,
+                max,
+                scale,
+                has_no_data,
+            } => {
+                let bar_width = 8 * width;
+                let bar_height = width / 3;
+                writeln!(
+                    svg,
+                    r#"    <text x="{}" y="{}">{}</text>"#,
+                    x,
+                    y,
+                    escape_xml(title)
+                )
+                .unwrap();
+                y += width / 4;
+                writeln!(
+                    svg,
+                    r#"    <rect x="{}" y="{}" width="{}" height="{}" fill="url(#legend-{})"/>"#,
+                    x, y, bar_width, bar_height, i
+                )
+                .unwrap();
+                y += bar_height + width / 4;
+                writeln!(
+                    svg,
+                    r#"    <text x="{}" y="{}" text-anchor="start">{}</text><text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                    x,
+                    y,
+                    format_number(*min),
+                    x + bar_width,
+                    y,
+                    format_number(*max)
+                )
+                .unwrap();
+                if scale.diverging && *min < 0.0 && *max > 0.0 {
+                    writeln!(
+                        svg,
+                        r#"    <text x="{}" y="{}" text-anchor="middle">0</text>"#,
+                        x + bar_width / 2,
+                        y
+                    )
+                    .unwrap();
+                }
+                if *has_no_data {
+                    y += width / 3;
+                    writeln!(
+                        svg,
+                        r#"    <rect x="{}" y="{}" width="{}" height="{}" fill="url(#no-data)"/><text x="{}" y="{}">no data</text>"#,
+                        x,
+                        y,
+                        bar_height,
+                        bar_height,
+                        x + bar_height + width / 10,
+                        y + bar_height / 2
+                    )
+                    .unwrap();
+                    y += bar_height;
+                }
+                y += width / 2;
+            }
+            Legend::Categorical { entries } => {
+                // Two columns, to fit above the transition metals:
+                let swatch = width / 3;
+                let row_height = width * 2 / 5;
+                let rows = (entries.len() as u32).div_ceil(2);
+                for (j, (label, color)) in entries.iter().enumerate() {
+                    let (column, row) = (j as u32 / rows, j as u32 % rows);
+                    let (entry_x, entry_y) = (x + column * 9 * width / 2, y + row * row_height);
+                    writeln!(
+                        svg,
+                        r#"    <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/><text x="{}" y="{}">{}</text>"#,
+                        entry_x,
+                        entry_y,
+                        swatch,
+                        swatch,
+                        color,
+                        entry_x + swatch + width / 10,
+                        entry_y + swatch / 2,
+                        escape_xml(label)
+                    )
+                    .unwrap();
+                }
+                y += rows * row_height + width / 2;
+            }
+        }
+    }
+    writeln!(svg, "  </g>").unwrap();
+}
+
+fn format_number(number: f64) -> String {
+    let formatted = format!("{:.3}", number);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn args(argv: &[&str]) -> cli::Args {
+        cli::Args::parse_from(
+            std::iter::once("periodic-table-generator").chain(argv.iter().copied()),
+        )
+    }
+
+    fn svg(argv: &[&str]) -> String {
+        let args = args(argv);
+        let elements = prepare_elements(&args).unwrap();
+        let tiles = make_tiles(&elements, &args);
+        let (tiles, colors, legends) = calculate_colors(&tiles, &args).unwrap();
+        generate_svg(&tiles, &colors, &legends, &args)
+    }
+
+    #[test]
+    fn test_phase_style() {
+        let styled = svg(&["--phase-style"]);
+        assert!(styled.contains(r#" class="gas">Ne</text>"#));
+        assert!(styled.contains(r#" class="liquid">Br</text>"#));
+        assert!(styled.contains(r#"">Fe</text>"#));
+        assert!(!svg(&[]).contains(r#" class="liquid">"#));
+        // Bromine boils at 332 K:
+        let hot = svg(&["--phase-style", "--temperature", "400"]);
+        assert!(hot.contains(r#" class="gas">Br</text>"#));
+    }
+
+    #[test]
+    fn test_as_of() {
+        let grey = args(&["--as-of", "1800"]);
+        let hide = args(&["--as-of", "1800", "--undiscovered", "hide"]);
+        let elements = prepare_elements(&grey).unwrap();
+        let tiles = make_tiles(&elements, &grey);
+        let undiscovered: Vec<String> = (tiles.iter().take(10))
+            .filter(|tile| tile.undiscovered)
+            .map(|tile| tile.element.symbol.to_string())
+            .collect();
+        assert_eq!(undiscovered, vec!["He", "Li", "B", "F", "Ne"]);
+        assert!(tiles.iter().all(|tile| !tile.is_hidden(&grey)));
+        assert!(tiles[9].is_hidden(&hide) && !tiles[7].is_hidden(&hide));
+        assert!(make_tiles(&elements, &args(&[]))
+            .iter()
+            .all(|tile| !tile.undiscovered));
+
+        let greyed = svg(&["--as-of", "1800"]);
+        assert!(greyed.contains(r#"<g class="undiscovered"><title>Neon</title>"#));
+        assert!(greyed.contains("<title>Oxygen</title>"));
+        let hidden = svg(&["--as-of", "1800", "--undiscovered", "hide"]);
+        assert!(!hidden.contains("<title>Neon</title>"));
+        assert!(hidden.contains("<title>Oxygen</title>"));
+
+        // Hidden elements don’t take part in colorings:
+        let hide = args(&[
+            "--as-of",
+            "1800",
+            "--undiscovered",
+            "hide",
+            "--gradient",
+            "discovered",
+        ]);
+        let tiles = make_tiles(&prepare_elements(&hide).unwrap(), &hide);
+        let (_, _, legends) = calculate_colors(&tiles, &hide).unwrap();
+        match &legends[..] {
+            [Legend::Gradient {
+                max, has_no_data, ..
+            }] => {
+                assert_eq!(*max, 1798.0); // Be
+                assert!(!has_no_data);
+            }
+            other => panic!("unexpected legends: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::elements::{Element, Isotope};
+use std::fmt::Write;
+
+/// A single isotope, as seen by `--mark` queries in the chart of nuclides.
+pub struct Nuclide<'a> {
+    pub element: &'a Element,
+    pub isotope: &'a Isotope,
+}
+
+impl Nuclide<'_> {
+    pub fn protons(&self) -> u16 {
+        self.element.atomic_number as u16
+    }
+
+    pub fn neutrons(&self) -> u16 {
+        self.isotope.mass_number - self.protons()
+    }
+}
+
+/// Chart of nuclides (Segrè chart) of all isotopes in the data set, with neutrons growing to the
+/// right and protons upwards. Stable nuclides are black, unless marked.
+pub fn generate_svg(elements: &[Element], args: &crate::cli::Args) -> Result<String, String> {
+    let width: u32 = 10;
+    let stroke_width: u32 = 1;
+    let padding: u32 = 3 * width;
+
+    let nuclides: Vec<Nuclide> = elements
+        .iter()
+        .flat_map(|element| {
+            element
+                .isotopes
+                .iter()
+                .map(move |isotope| Nuclide { element, isotope })
+        })
+        .collect();
+
+    let max_n = nuclides.iter().map(Nuclide::neutrons).max().unwrap_or(0) as u32;
+    let max_z = nuclides.iter().map(Nuclide::protons).max().unwrap_or(0) as u32;
+    let position = |nuclide: &Nuclide| {
+        (
+            padding + nuclide.neutrons() as u32 * width,
+            padding + (max_z - nuclide.protons() as u32) * width,
+        )
+    };
+
+    let (viewbox_width, viewbox_height) = (
+        2 * padding + (max_n + 1) * width,
+        2 * padding + (max_z + 1) * width,
+    );
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" viewBox="0 0 {} {}">"#,
+        viewbox_width, viewbox_height,
+    );
+
+    writeln!(
+        svg,
+        r#"
+  <desc>
+    Created with https://github.com/michalrus/periodic-table-generator
+    ❯ periodic-table-generator {}{}
+  </desc>
+  <style>
+    .axes text {{ font-size: {}px; fill: #808080; text-anchor: middle; alignment-baseline: middle; }}"#,
+        crate::escape_xml(&crate::cli::escaped_argv()),
+        crate::escape_xml(&crate::cli::override_notes(args)),
+        width * 7 / 10,
+    )
+    .unwrap();
+
+    crate::write_tile_styles(
+        &mut svg,
+        "nuclides",
+        width,
+        stroke_width,
+        &crate::mark_colors(args),
+    );
+
+    svg.push_str("  </style>\n");
+
+    writeln!(svg, r#"  <g class="nuclides">"#).unwrap();
+
+    for nuclide in nuclides.iter() {
+        let (x, y) = position(nuclide);
+        let marks = crate::mark_classes(nuclide, args)?;
+        write!(
+            svg,
+            "    <g><title>{}-{}</title>",
+            crate::escape_xml(nuclide.element.display_name()),
+            nuclide.isotope.mass_number
+        )
+        .unwrap();
+        let fill = if nuclide.isotope.is_stable() {
+            Some("black")
+        } else {
+            None
+        };
+        crate::write_tile_rects(&mut svg, x, y, width, fill, &marks);
+        writeln!(svg, "</g>").unwrap();
+    }
+
+    svg.push_str("  </g>\n");
+
+    // Element symbols left of the lightest isotope, and every tenth neutron number below:
+    write!(svg, r#"  <g class="axes">"#).unwrap();
+    for element in elements.iter() {
+        let lightest = nuclides
+            .iter()
+            .filter(|nuclide| nuclide.element.atomic_number == element.atomic_number)
+            .min_by_key(|nuclide| nuclide.neutrons());
+        if let Some(nuclide) = lightest {
+            let (x, y) = position(nuclide);
+            write!(
+                svg,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                x - width,
+                y + width / 2,
+                element.symbol
+            )
+            .unwrap();
+        }
+    }
+    for n in (0..=max_n).step_by(10) {
+        write!(
+            svg,
+            r#"<text x="{}" y="{}">{}</text>"#,
+            padding + n * width + width / 2,
+            padding + (max_z + 1) * width + width,
+            n
+        )
+        .unwrap();
+    }
+    svg.push_str("</g>\n");
+
+    svg.push_str("</svg>");
+
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::Query;
+    use clap::Parser;
+
+    #[test]
+    fn test_nuclide_lookup() {
+        let hydrogen = &crate::elements::ALL[0];
+        let tritium = Nuclide {
+            element: hydrogen,
+            isotope: &hydrogen.isotopes[2],
+        };
+        let evaluate = |source: &str| Query::new(source).unwrap().evaluate_on(&tritium);
+        assert_eq!(evaluate("n == 2 && a == 3 && z == 1"), Ok(true));
+        assert_eq!(evaluate(r#"decay_mode == "beta-" && !stable"#), Ok(true));
+        assert_eq!(evaluate("half_life > 12 * 365 * 86400"), Ok(true));
+        assert_eq!(evaluate("abundance == null"), Ok(true));
+        assert_eq!(evaluate(r#"name == "Hydrogen""#), Ok(true));
+
+        let protium = Nuclide {
+            element: hydrogen,
+            isotope: &hydrogen.isotopes[0],
+        };
+        let query = Query::new("stable && half_life == null && decay_mode == null && n == 0");
+        assert_eq!(query.unwrap().evaluate_on(&protium), Ok(true));
+    }
+
+    #[test]
+    fn test_generate_svg() {
+        let args = crate::cli::Args::parse_from(["periodic-table-generator", "--nuclides"]);
+        let svg = generate_svg(&crate::elements::ALL[..2], &args).unwrap();
+        // Protons grow upwards, from 2 at the top, and neutrons to the right:
+        let tile = |title: &str| {
+            let start = svg.find(&format!("<title>{}</title>", title)).unwrap();
+            svg[start..].split("</g>").next().unwrap().to_string()
+        };
+        assert!(tile("Hydrogen-1").contains(r#"<rect x="30" y="40" fill="black"/>"#));
+        assert!(tile("Hydrogen-3").contains(r#"<rect x="50" y="40"/>"#));
+        assert!(tile("Helium-4").contains(r#"<rect x="50" y="30" fill="black"/>"#));
+        assert!(svg.contains(r#"<text x="20" y="45">H</text>"#));
+
+        let args = crate::cli::Args::parse_from([
+            "periodic-table-generator",
+            "--nuclides",
+            "--mark",
+            "red: !stable",
+        ]);
+        let svg = generate_svg(&crate::elements::ALL[..1], &args).unwrap();
+        assert!(svg.contains(r#"<rect x="50" y="30" class="mark-0"/>"#));
+        assert!(svg.contains(r#"<rect x="30" y="30" fill="black"/>"#));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::elements::{ElectronConfiguration, Element};
+use std::fmt::Write;
+
+/// Orbital box diagram of a single element (or its ion), one box per orbital, with electrons
+/// placed according to Hund’s rule.
+pub fn generate_svg(element: &Element, args: &crate::cli::Args) -> Result<String, String> {
+    let box_size: u32 = 30;
+    let gap: u32 = 20;
+    let padding: u32 = 10;
+    let label_height: u32 = 25;
+
+    let config = match args.charge {
+        0 => element.electron_configuration.clone(),
+        charge => ElectronConfiguration::ion(element.atomic_number, charge)?,
+    };
+    let subshells = if args.valence_only {
+        config.valence()
+    } else {
+        config.subshells.clone()
+    };
+
+    let num_boxes: u32 = subshells.iter().map(|(s, _)| 2 * s.l as u32 + 1).sum();
+    let num_gaps = subshells.len().saturating_sub(1) as u32;
+    let (viewbox_width, viewbox_height) = (
+        2 * padding + num_boxes * box_size + num_gaps * gap,
+        2 * padding + box_size + label_height,
+    );
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" viewBox="0 0 {} {}">"#,
+        viewbox_width, viewbox_height,
+    );
+
+    writeln!(
+        svg,
+        r#"
+  <desc>
+    Created with https://github.com/michalrus/periodic-table-generator
+    ❯ periodic-table-generator {}{}
+  </desc>
+  <style>
+    .orbitals rect {{ fill: white; stroke: black; stroke-width: 1; width: {}px; height: {}px; }}
+    .orbitals path {{ fill: none; stroke: black; stroke-width: 1.5; stroke-linecap: round; stroke-linejoin: round; }}
+    .orbitals text {{ font-size: {}px; text-anchor: middle; alignment-baseline: middle; }}
+  </style>
+  <g class="orbitals">"#,
+        crate::escape_xml(&crate::cli::escaped_argv()),
+        crate::escape_xml(&crate::cli::override_notes(args)),
+        box_size,
+        box_size,
+        box_size / 2,
+    )
+    .unwrap();
+
+    let mut x = padding;
+    let y = padding;
+    for (subshell, electrons) in subshells.iter() {
+        let num_orbitals = 2 * subshell.l as u32 + 1;
+
+        // Hund’s rule: first one electron in each orbital, then pairing.
+        let spins = |orbital: u32| -> (bool, bool) {
+            let electrons = *electrons as u32;
+            (orbital < electrons, orbital + num_orbitals < electrons)
+        };
+
+        write!(svg, "    ").unwrap();
+        for orbital in 0..num_orbitals {
+            let box_x = x + orbital * box_size;
+            write!(svg, r#"<rect x="{}" y="{}"/>"#, box_x, y).unwrap();
+            let (up, down) = spins(orbital);
+            if up {
+                write_arrow(&mut svg, box_x + box_size / 3, y, box_size, true);
+            }
+            if down {
+                write_arrow(&mut svg, box_x + box_size * 2 / 3, y, box_size, false);
+            }
+        }
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}">{}</text>"#,
+            x + num_orbitals * box_size / 2,
+            y + box_size + label_height / 2,
+            subshell
+        )
+        .unwrap();
+
+        x += num_orbitals * box_size + gap;
+    }
+
+    svg.push_str("  </g>\n");
+    svg.push_str("</svg>");
+
+    Ok(svg)
+}
+
+fn write_arrow(svg: &mut String, x: u32, y: u32, box_size: u32, up: bool) {
+    let (top, bottom) = (y + box_size / 6, y + box_size * 5 / 6);
+    let head = box_size / 8;
+    let (tail, tip, back) = if up {
+        (bottom, top, top + head)
+    } else {
+        (top, bottom, bottom - head)
+    };
+    write!(
+        svg,
+        r#"<path d="M{x},{tail} L{x},{tip} M{l},{back} L{x},{tip} L{r},{back}"/>"#,
+        x = x,
+        tail = tail,
+        tip = tip,
+        back = back,
+        l = x - head,
+        r = x + head,
+    )
+    .unwrap();
+}
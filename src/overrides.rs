@@ -0,0 +1,290 @@
+use crate::elements::{Element, OxidationStates, Symbol};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+
+/// A correction of the oxidation states of a single element, e.g. `Mn.common={2,4,7}`, or
+/// `Mn.notable+={4}`, or `Mn.notable-={4}`.
+#[derive(Debug, Clone)]
+pub struct OxidationOverride {
+    pub symbol: String,
+    pub kind: Kind,
+    pub operation: Operation,
+    pub states: BTreeSet<i8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Common,
+    Notable,
+    Predicted,
+    CitationNeeded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Set,
+    Add,
+    Remove,
+}
+
+impl Kind {
+    const ALL: [Kind; 4] = [
+        Kind::Common,
+        Kind::Notable,
+        Kind::Predicted,
+        Kind::CitationNeeded,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Common => "common",
+            Kind::Notable => "notable",
+            Kind::Predicted => "predicted",
+            Kind::CitationNeeded => "citation_needed",
+        }
+    }
+
+    fn get<'a>(&self, states: &'a OxidationStates) -> &'a BTreeSet<i8> {
+        match self {
+            Kind::Common => &states.common,
+            Kind::Notable => &states.notable,
+            Kind::Predicted => &states.predicted,
+            Kind::CitationNeeded => &states.citation_needed,
+        }
+    }
+
+    fn of<'a>(&self, states: &'a mut OxidationStates) -> &'a mut BTreeSet<i8> {
+        match self {
+            Kind::Common => &mut states.common,
+            Kind::Notable => &mut states.notable,
+            Kind::Predicted => &mut states.predicted,
+            Kind::CitationNeeded => &mut states.citation_needed,
+        }
+    }
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Set => "=",
+            Operation::Add => "+=",
+            Operation::Remove => "-=",
+        }
+    }
+}
+
+impl OxidationOverride {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        static RE: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r#"^\s*([A-Z][a-z]*)\.(\w+)\s*(=|\+=|-=)\s*\{([^}]*)\}\s*$"#).unwrap()
+        });
+        let cpt = RE.captures(input).ok_or_else(|| {
+            format!(
+                "expected SYMBOL.KIND={{STATES}}, e.g. Mn.common={{2,4,7}}, got: {}",
+                input
+            )
+        })?;
+        let kind = Kind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == &cpt[2])
+            .ok_or_else(|| {
+                format!(
+                    "unknown kind of oxidation states: {}, expected one of: {}",
+                    &cpt[2],
+                    Kind::ALL.map(|kind| kind.as_str()).join(", ")
+                )
+            })?;
+        let operation = match &cpt[3] {
+            "=" => Operation::Set,
+            "+=" => Operation::Add,
+            _ => Operation::Remove,
+        };
+        let states = cpt[4]
+            .split(',')
+            .map(str::trim)
+            .filter(|state| !state.is_empty())
+            .map(|state| {
+                state
+                    .parse::<i8>()
+                    .map_err(|_| format!("invalid oxidation state: {}", state))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            symbol: cpt[1].to_string(),
+            kind,
+            operation,
+            states,
+        })
+    }
+
+    /// Patch files have one override per line, with `#` comments and empty lines ignored.
+    pub fn parse_file(path: &str) -> Result<Vec<Self>, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        text.lines()
+            .enumerate()
+            .map(|(idx, line)| (idx, line.split('#').next().unwrap_or("")))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                Self::parse(line).map_err(|err| format!("{}:{}: {}", path, idx + 1, err))
+            })
+            .collect()
+    }
+
+    /// States added to one kind are removed from all the others, so e.g. `Mn.common+={4}` moves
+    /// +4 from notable to common.
+    pub fn apply(&self, elements: &mut [Element]) -> Result<(), String> {
+        let element = elements
+            .iter_mut()
+            .find(|element| element.symbol.to_string() == self.symbol)
+            .ok_or_else(|| format!("Unknown element symbol in --override-ox: {}", self.symbol))?;
+        let states = &mut element.oxidation_states;
+        match self.operation {
+            Operation::Set => *self.kind.of(states) = self.states.clone(),
+            Operation::Add => self.kind.of(states).extend(self.states.iter()),
+            Operation::Remove => self
+                .kind
+                .of(states)
+                .retain(|state| !self.states.contains(state)),
+        }
+        if self.operation != Operation::Remove {
+            for kind in Kind::ALL.iter().filter(|&&kind| kind != self.kind) {
+                kind.of(states).retain(|state| !self.states.contains(state));
+            }
+        }
+        states.normalize();
+        Ok(())
+    }
+}
+
+/// Overrides turning `old` into `new`, each with the states it replaces. Elements go in the
+/// order of atomic numbers, including `--extended`, followed by symbols unknown here.
+pub fn diff(
+    old: &HashMap<Symbol, OxidationStates>,
+    new: &HashMap<Symbol, OxidationStates>,
+) -> Vec<(OxidationOverride, BTreeSet<i8>)> {
+    let known: Vec<Symbol> = (crate::elements::ALL.iter())
+        .chain(crate::elements::EXTENDED.iter())
+        .map(|element| element.symbol.clone())
+        .collect();
+    let unknown: BTreeSet<&Symbol> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|symbol| !known.contains(symbol))
+        .collect();
+
+    let empty = OxidationStates::default();
+    known
+        .iter()
+        .chain(unknown)
+        .flat_map(|symbol| {
+            let old = old.get(symbol).unwrap_or(&empty);
+            let new = new.get(symbol).unwrap_or(&empty);
+            Kind::ALL
+                .into_iter()
+                .filter(|kind| kind.get(old) != kind.get(new))
+                .map(move |kind| {
+                    let replacing = OxidationOverride {
+                        symbol: symbol.to_string(),
+                        kind,
+                        operation: Operation::Set,
+                        states: kind.get(new).clone(),
+                    };
+                    (replacing, kind.get(old).clone())
+                })
+        })
+        .collect()
+}
+
+impl std::fmt::Display for OxidationOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}{}{{{}}}",
+            self.symbol,
+            self.kind.as_str(),
+            self.operation.as_str(),
+            self.states
+                .iter()
+                .map(|state| state.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(overrides: &[&str]) -> Result<OxidationStates, String> {
+        let mut elements = crate::elements::ALL.clone();
+        for oxidation_override in overrides.iter() {
+            OxidationOverride::parse(oxidation_override)?.apply(&mut elements)?;
+        }
+        Ok(elements[24].oxidation_states.clone()) // Mn
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = OxidationOverride::parse(" Mn.notable -= {4,-1 , }").unwrap();
+        assert_eq!(parsed.symbol, "Mn");
+        assert_eq!(parsed.kind, Kind::Notable);
+        assert_eq!(parsed.operation, Operation::Remove);
+        assert_eq!(parsed.states, BTreeSet::from([-1, 4]));
+        assert_eq!(parsed.to_string(), "Mn.notable-={-1, 4}");
+
+        let error = |input| OxidationOverride::parse(input).unwrap_err();
+        assert_eq!(
+            error("Mn.usual={2}"),
+            "unknown kind of oxidation states: usual, expected one of: common, notable, predicted, citation_needed"
+        );
+        assert_eq!(error("Mn.common={2, x}"), "invalid oxidation state: x");
+        assert_eq!(error("Mn.common={200}"), "invalid oxidation state: 200");
+        assert!(error("Mn.common=2").starts_with("expected SYMBOL.KIND={STATES}"));
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let path = std::env::temp_dir().join("periodic-table-generator-overrides.txt");
+        let path = path.to_str().unwrap();
+        std::fs::write(
+            path,
+            "# Corrections\n\nMn.common={2, 4} # fewer\nFe.common+={6}\n",
+        )
+        .unwrap();
+        let parsed = OxidationOverride::parse_file(path).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].to_string(), "Fe.common+={6}");
+        std::fs::write(path, "Mn.common={2}\n\nMn.common:={2}\n").unwrap();
+        let error = OxidationOverride::parse_file(path).unwrap_err();
+        std::fs::remove_file(path).unwrap();
+        assert!(error.starts_with(&format!("{}:3: expected", path)));
+    }
+
+    #[test]
+    fn test_apply() {
+        let states = apply(&[]).unwrap();
+        assert_eq!(states.common, BTreeSet::from([2, 4, 7]));
+        assert_eq!(states.notable, BTreeSet::from([-3, -1, 1, 3, 5, 6]));
+
+        // Added to one kind is removed from the others:
+        let states = apply(&["Mn.common+={3}", "Mn.citation_needed+={4}"]).unwrap();
+        assert_eq!(states.common, BTreeSet::from([2, 3, 7]));
+        assert_eq!(states.notable, BTreeSet::from([-3, -1, 1, 5, 6]));
+        assert_eq!(states.citation_needed, BTreeSet::from([0, 4]));
+        // Sources stay with the state:
+        assert!(states.sources.contains_key(&3));
+
+        // But removed from one kind isn’t added to another:
+        let states = apply(&["Mn.common-={2}", "Mn.notable={6}"]).unwrap();
+        assert_eq!(states.common, BTreeSet::from([4, 7]));
+        assert_eq!(states.notable, BTreeSet::from([6]));
+        assert_eq!(states.sources.keys().collect::<Vec<_>>(), vec![&6]);
+
+        assert_eq!(
+            apply(&["Xx.common={1}"]).unwrap_err(),
+            "Unknown element symbol in --override-ox: Xx"
+        );
+    }
+}
@@ -1,8 +1,13 @@
+pub use eval::Subject;
 use expr::*;
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct Query {
+    source: String,
     compiled: Expr,
+    /// Shared between clones, so that each pattern is compiled once, not once per element.
+    regexes: Arc<eval::Regexes>,
 }
 
 impl Query {
@@ -21,14 +26,26 @@ impl Query {
                 "Error parsing Query: {}",
                 nom::error::convert_error(input, err)
             )),
-            Err(err) => Err(format!("Error parsing Query: {}", err.to_string())),
+            Err(err) => Err(format!("Error parsing Query: {}", err)),
         }?;
 
-        Ok(Self { compiled })
+        let regexes = eval::Regexes::default();
+        compiled
+            .walk(&mut |expr| {
+                eval::check_call(expr)?;
+                regexes.precompile(expr)
+            })
+            .map_err(|err| format!("Error parsing Query: {}", err))?;
+
+        Ok(Self {
+            source: input.trim().to_string(),
+            compiled,
+            regexes: Arc::new(regexes),
+        })
     }
 
-    pub fn evaluate_on(&self, element: &crate::elements::Element) -> Result<bool, String> {
-        match eval::Value::eval(&self.compiled, element)? {
+    pub fn evaluate_on(&self, subject: &dyn Subject) -> Result<bool, String> {
+        match eval::Value::eval(&self.compiled, subject, &self.regexes)? {
             eval::Value::Bool(b) => Ok(b),
             other => Err(format!(
                 "Query did not evaluate to a boolean value but to {:?}.",
@@ -36,62 +53,450 @@ impl Query {
             )),
         }
     }
+
+    /// For continuous colorings, `None` means missing data.
+    pub fn evaluate_number_on(&self, subject: &dyn Subject) -> Result<Option<f64>, String> {
+        match eval::Value::eval(&self.compiled, subject, &self.regexes)? {
+            eval::Value::Null => Ok(None),
+            eval::Value::Int(a) => Ok(Some(a as f64)),
+            eval::Value::Decimal(a) => Ok(Some(a.into())),
+            other => Err(format!(
+                "Query did not evaluate to a number but to {:?}.",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
 }
 
 mod eval {
-    use std::collections::BTreeSet;
+    use super::decimal::Decimal;
+    use regex::Regex;
+    use std::collections::{BTreeSet, HashMap};
+    use std::sync::Mutex;
 
     #[derive(PartialEq, Eq, Debug, Clone, PartialOrd, Ord)]
     pub enum Value {
+        /// Missing data, e.g. the electronegativity of neon.
+        Null,
         Bool(bool),
         Int(i32),
+        Decimal(Decimal),
+        Str(String),
         Set(BTreeSet<Value>),
     }
 
     use super::expr;
     use super::expr::Expr;
 
-    impl Value {
-        pub fn eval(expr: &Expr, element: &crate::elements::Element) -> Result<Self, String> {
-            //Ok(Self::Bool(false))
+    /// Also for division by zero.
+    fn overflow(op: &str, l: &dyn std::fmt::Display, r: &dyn std::fmt::Display) -> String {
+        format!(
+            "Eval: arithmetic overflow or division by zero in ‘{} {} {}’",
+            l, op, r
+        )
+    }
 
-            fn bset_to_value(xs: &BTreeSet<i8>) -> Value {
-                Value::Set(xs.iter().map(|&a| Value::Int(a as i32)).collect())
+    /// Compiled patterns of `~` and `!~`, by their source.
+    #[derive(Debug, Default)]
+    pub struct Regexes(Mutex<HashMap<String, Regex>>);
+
+    impl Regexes {
+        pub fn get(&self, pattern: &str) -> Result<Regex, String> {
+            let mut compiled = self.0.lock().unwrap();
+            if let Some(regex) = compiled.get(pattern) {
+                return Ok(regex.clone());
+            }
+            let regex = Regex::new(pattern)
+                .map_err(|err| format!("invalid regular expression: {}", err))?;
+            compiled.insert(pattern.to_string(), regex.clone());
+            Ok(regex)
+        }
+
+        /// Literal patterns are compiled right away, so that their errors are reported early.
+        /// Others, like `"^" + symbol`, only once they’re evaluated.
+        pub fn precompile(&self, expr: &Expr) -> Result<(), String> {
+            if let Expr::BinaryOp(
+                expr::BinaryOperator::Matches | expr::BinaryOperator::NotMatches,
+                _,
+                r,
+            ) = expr
+            {
+                if let Expr::LStr(pattern) = r.as_ref() {
+                    self.get(pattern)?;
+                }
             }
+            Ok(())
+        }
+    }
+
+    /// Built-in functions, e.g. `len(oxidation_states.common)`. They all return null for null
+    /// arguments.
+    struct Function {
+        name: &'static str,
+        arity: usize,
+        call: fn(&[Value]) -> Result<Value, String>,
+    }
+
+    static FUNCTIONS: &[Function] = &[
+        Function {
+            name: "len",
+            arity: 1,
+            call: |args| match &args[0] {
+                Value::Set(a) => Ok(Value::Int(a.len() as i32)),
+                Value::Str(a) => Ok(Value::Int(a.chars().count() as i32)),
+                other => Err(format!("expects a set or a string, but got {:?}", other)),
+            },
+        },
+        Function {
+            name: "min",
+            arity: 1,
+            call: |args| match &args[0] {
+                Value::Set(a) => Ok(a.first().cloned().unwrap_or(Value::Null)),
+                other => Err(format!("expects a set, but got {:?}", other)),
+            },
+        },
+        Function {
+            name: "max",
+            arity: 1,
+            call: |args| match &args[0] {
+                Value::Set(a) => Ok(a.last().cloned().unwrap_or(Value::Null)),
+                other => Err(format!("expects a set, but got {:?}", other)),
+            },
+        },
+        Function {
+            name: "abs",
+            arity: 1,
+            call: |args| match &args[0] {
+                Value::Int(a) => a
+                    .checked_abs()
+                    .map(Value::Int)
+                    .ok_or_else(|| format!("overflows for {}", a)),
+                Value::Decimal(a) if *a < Decimal::from(0) => a
+                    .checked_neg()
+                    .map(Value::Decimal)
+                    .ok_or_else(|| format!("overflows for {}", a)),
+                Value::Decimal(a) => Ok(Value::Decimal(*a)),
+                other => Err(format!("expects a number, but got {:?}", other)),
+            },
+        },
+        Function {
+            name: "sum",
+            arity: 1,
+            call: |args| match &args[0] {
+                Value::Set(a) => a.iter().try_fold(Value::Int(0), |acc, x| match (acc, x) {
+                    (Value::Int(acc), Value::Int(x)) => acc
+                        .checked_add(*x)
+                        .map(Value::Int)
+                        .ok_or_else(|| "overflows".to_string()),
+                    (_, other) => Err(format!("expects a set of integers, but got {:?}", other)),
+                }),
+                other => Err(format!("expects a set, but got {:?}", other)),
+            },
+        },
+    ];
+
+    /// Unknown functions and wrong numbers of arguments are reported before evaluation.
+    pub fn check_call(expr: &Expr) -> Result<(), String> {
+        if let Expr::Call(name, args) = expr {
+            let function = FUNCTIONS
+                .iter()
+                .find(|function| function.name == name)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown function: {}(), expected one of: {}",
+                        name,
+                        FUNCTIONS
+                            .iter()
+                            .map(|function| function.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+            if args.len() != function.arity {
+                return Err(format!(
+                    "{}() takes {} argument(s), but got {}",
+                    name,
+                    function.arity,
+                    args.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Anything queries can be evaluated on, e.g. an element or a nuclide.
+    pub trait Subject {
+        /// `None` for unknown symbols, and errors for data that isn’t a valid value, e.g. decimal
+        /// numbers in a set from `--data`.
+        fn lookup(&self, symbol: &str) -> Result<Option<Value>, String>;
+    }
+
+    /// The variable of a set comprehension, shadowing a symbol of the same name.
+    struct Binding<'a> {
+        variable: &'a str,
+        value: Value,
+        subject: &'a dyn Subject,
+    }
+
+    impl Subject for Binding<'_> {
+        fn lookup(&self, symbol: &str) -> Result<Option<Value>, String> {
+            if symbol == self.variable {
+                Ok(Some(self.value.clone()))
+            } else {
+                self.subject.lookup(symbol)
+            }
+        }
+    }
+
+    /// So that e.g. `z in 0..1000000000` fails instead of filling the memory.
+    const MAX_RANGE_LEN: i64 = 100_000;
+
+    fn set_item(value: Value) -> Result<Value, String> {
+        match value {
+            Value::Decimal(a) => Err(format!(
+                "Eval: decimal numbers cannot be put in sets, but got {}; use comparisons instead",
+                a
+            )),
+            other => Ok(other),
+        }
+    }
+
+    fn bset_to_value(xs: &BTreeSet<i8>) -> Value {
+        Value::Set(xs.iter().map(|&a| Value::Int(a as i32)).collect())
+    }
+
+    fn option_to_value(x: Option<f64>) -> Value {
+        x.map_or(Value::Null, |a| Value::Decimal(Decimal::from(a)))
+    }
+
+    /// Objects aren’t supported, and are treated as missing data.
+    fn json_to_value(json: &serde_json::Value) -> Result<Value, String> {
+        Ok(match json {
+            serde_json::Value::Bool(a) => Value::Bool(*a),
+            serde_json::Value::Number(a) => match a.as_i64().map(i32::try_from) {
+                Some(Ok(a)) => Value::Int(a),
+                _ => a
+                    .as_f64()
+                    .map_or(Value::Null, |a| Value::Decimal(Decimal::from(a))),
+            },
+            serde_json::Value::String(a) => Value::Str(a.clone()),
+            serde_json::Value::Array(a) => Value::Set(
+                a.iter()
+                    .map(|a| set_item(json_to_value(a)?))
+                    .collect::<Result<_, _>>()?,
+            ),
+            serde_json::Value::Null | serde_json::Value::Object(_) => Value::Null,
+        })
+    }
 
+    impl Subject for crate::elements::Element {
+        fn lookup(&self, symbol: &str) -> Result<Option<Value>, String> {
+            Ok(match symbol {
+                "atomic_number" | "z" | "Z" => Some(Value::Int(self.atomic_number as i32)),
+                "symbol" => Some(Value::Str(self.symbol.to_string())),
+                "name" => Some(Value::Str(self.name.clone())),
+                "local_name" => Some(Value::Str(self.display_name().to_string())),
+                "group" => Some(Value::Int(self.group.map_or(-1, |a| a as i32))),
+                "period" => Some(Value::Int(self.period as i32)),
+                "block" => Some(Value::Int(self.block as i32)),
+                "category" => Some(Value::Str(self.category.to_string())),
+                "mass" | "atomic_weight" => Some(option_to_value(
+                    self.atomic_weight.map(|atomic_weight| atomic_weight.value),
+                )),
+                "electronegativity" => Some(option_to_value(self.electronegativity)),
+                "covalent_radius_pm" | "covalent_radius" => {
+                    Some(option_to_value(self.covalent_radius_pm))
+                }
+                "van_der_waals_radius_pm" | "van_der_waals_radius" => {
+                    Some(option_to_value(self.van_der_waals_radius_pm))
+                }
+                "ionization_energy.first_kj_per_mol" | "ionization_energy.first" => {
+                    Some(option_to_value(self.ionization_energy.first_kj_per_mol))
+                }
+                "electron_affinity_kj_per_mol" | "electron_affinity" => {
+                    Some(option_to_value(self.electron_affinity_kj_per_mol))
+                }
+                "phase" => Some(Value::Str(self.phase.to_string())),
+                "phase_at_t" => Some(Value::Str(self.phase_at_t.to_string())),
+                "melting_point" => Some(option_to_value(self.melting_point)),
+                "boiling_point" => Some(option_to_value(self.boiling_point)),
+                "isotopes" => Some(Value::Set(
+                    self.isotopes
+                        .iter()
+                        .map(|i| Value::Int(i.mass_number as i32))
+                        .collect(),
+                )),
+                "stable_isotopes" => Some(Value::Set(
+                    self.isotopes
+                        .iter()
+                        .filter(|i| i.is_stable())
+                        .map(|i| Value::Int(i.mass_number as i32))
+                        .collect(),
+                )),
+                "radioactive" => Some(Value::Bool(self.is_radioactive())),
+                "discovered" | "discovery.year" => Some(
+                    self.discovery
+                        .as_ref()
+                        .map_or(Value::Null, |a| Value::Int(a.year as i32)),
+                ),
+                "discovery.antiquity" => Some(
+                    self.discovery
+                        .as_ref()
+                        .map_or(Value::Null, |a| Value::Bool(a.antiquity)),
+                ),
+                "discovery.discoverer" => Some(
+                    self.discovery
+                        .as_ref()
+                        .and_then(|a| a.discoverer.as_deref())
+                        .map_or(Value::Null, |a| Value::Str(a.to_string())),
+                ),
+                "discovery.country" => Some(
+                    self.discovery
+                        .as_ref()
+                        .and_then(|a| a.country.as_deref())
+                        .map_or(Value::Null, |a| Value::Str(a.to_string())),
+                ),
+                "electron_configuration.full" | "config.full" => {
+                    Some(Value::Str(self.electron_configuration.full()))
+                }
+                "electron_configuration.abbreviated" | "config.abbreviated" => {
+                    Some(Value::Str(self.electron_configuration.abbreviated()))
+                }
+                "electron_configuration.s" | "config.s" => {
+                    Some(Value::Int(self.electron_configuration.occupancy(0) as i32))
+                }
+                "electron_configuration.p" | "config.p" => {
+                    Some(Value::Int(self.electron_configuration.occupancy(1) as i32))
+                }
+                "electron_configuration.d" | "config.d" => {
+                    Some(Value::Int(self.electron_configuration.occupancy(2) as i32))
+                }
+                "electron_configuration.f" | "config.f" => {
+                    Some(Value::Int(self.electron_configuration.occupancy(3) as i32))
+                }
+                "valence_electrons" => Some(Value::Int(
+                    self.electron_configuration.valence_electrons() as i32,
+                )),
+                "oxidation_states.common" => Some(bset_to_value(&self.oxidation_states.common)),
+                "oxidation_states.notable" => Some(bset_to_value(&self.oxidation_states.notable)),
+                "oxidation_states.predicted" => {
+                    Some(bset_to_value(&self.oxidation_states.predicted))
+                }
+                "oxidation_states.citation_needed" => {
+                    Some(bset_to_value(&self.oxidation_states.citation_needed))
+                }
+                // Missing in some elements is the same as missing data:
+                other => match other.strip_prefix("extra.") {
+                    None => None,
+                    Some(key) => Some(self.extra.get(key).map_or(Ok(Value::Null), |json| {
+                        json_to_value(json).map_err(|err| format!("{} ({})", err, other))
+                    })?),
+                },
+            })
+        }
+    }
+
+    impl Subject for crate::nuclides::Nuclide<'_> {
+        fn lookup(&self, symbol: &str) -> Result<Option<Value>, String> {
+            Ok(match symbol {
+                "n" | "N" => Some(Value::Int(self.neutrons() as i32)),
+                "a" | "A" | "mass_number" => Some(Value::Int(self.isotope.mass_number as i32)),
+                "half_life" => Some(option_to_value(self.isotope.half_life)),
+                "abundance" => Some(option_to_value(self.isotope.abundance)),
+                "decay_mode" => Some(
+                    self.isotope
+                        .decay_mode
+                        .map_or(Value::Null, |a| Value::Str(a.to_string())),
+                ),
+                "stable" => Some(Value::Bool(self.isotope.is_stable())),
+                // Everything else, including `z`, is about the element:
+                _ => self.element.lookup(symbol)?,
+            })
+        }
+    }
+
+    impl Value {
+        pub fn eval(expr: &Expr, subject: &dyn Subject, regexes: &Regexes) -> Result<Self, String> {
             match expr {
+                Expr::LNull => Ok(Value::Null),
                 Expr::LBool(a) => Ok(Value::Bool(*a)),
                 Expr::LInt(a) => Ok(Value::Int(*a)),
-                Expr::Symbol(symb) => match symb.as_str() {
-                    "atomic_number" | "z" | "Z" => Ok(Value::Int(element.atomic_number as i32)),
-                    "group" => Ok(Value::Int(element.group.map_or(-1, |a| a as i32))),
-                    "period" => Ok(Value::Int(element.period as i32)),
-                    "block" => Ok(Value::Int(element.block as i32)),
-                    "oxidation_states.common" => {
-                        Ok(bset_to_value(&element.oxidation_states.common))
-                    }
-                    "oxidation_states.notable" => {
-                        Ok(bset_to_value(&element.oxidation_states.notable))
-                    }
-                    "oxidation_states.predicted" => {
-                        Ok(bset_to_value(&element.oxidation_states.predicted))
+                Expr::LDecimal(a) => Ok(Value::Decimal(*a)),
+                Expr::LStr(a) => Ok(Value::Str(a.clone())),
+                Expr::Symbol(symb) => subject
+                    .lookup(symb)?
+                    .ok_or_else(|| format!("Eval: unknown symbol: {}", symb)),
+                Expr::Call(name, subexprs) => {
+                    check_call(expr).map_err(|err| format!("Eval: {}", err))?;
+                    let function = FUNCTIONS.iter().find(|f| f.name == name).unwrap();
+                    let args = subexprs
+                        .iter()
+                        .map(|sx| Value::eval(sx, subject, regexes))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if args.contains(&Value::Null) {
+                        return Ok(Value::Null);
                     }
-                    "oxidation_states.citation_needed" => {
-                        Ok(bset_to_value(&element.oxidation_states.citation_needed))
-                    }
-                    other => Err(format!("Eval: unknown symbol: {}", other)),
-                },
+                    (function.call)(&args).map_err(|err| format!("Eval: {}() {}", name, err))
+                }
                 Expr::LSet(subexprs) => Ok(Value::Set(
                     subexprs
-                        .into_iter()
-                        .map(|sx| Value::eval(sx, element))
+                        .iter()
+                        .map(|sx| set_item(Value::eval(sx, subject, regexes)?))
                         .collect::<Result<BTreeSet<_>, _>>()?,
                 )),
+                Expr::LSetComprehension(element, variable, set, filter) => {
+                    let items = match Value::eval(set, subject, regexes)? {
+                        Value::Set(items) => items,
+                        Value::Null => return Ok(Value::Null),
+                        other => {
+                            return Err(format!(
+                                "Eval: set comprehensions need a set after ‘in’, but got {:?}",
+                                other
+                            ))
+                        }
+                    };
+                    let mut result = BTreeSet::new();
+                    for value in items {
+                        let binding = Binding {
+                            variable,
+                            value,
+                            subject,
+                        };
+                        if let Some(filter) = filter {
+                            match Value::eval(filter, &binding, regexes)? {
+                                Value::Bool(true) => {}
+                                Value::Bool(false) => continue,
+                                other => {
+                                    return Err(format!(
+                                        "Eval: set comprehension filters must be booleans, but got {:?}",
+                                        other
+                                    ))
+                                }
+                            }
+                        }
+                        result.insert(set_item(Value::eval(element, &binding, regexes)?)?);
+                    }
+                    Ok(Value::Set(result))
+                }
                 Expr::UnaryOp(op, subexpr) => {
-                    let subval = Value::eval(subexpr, element)?;
+                    let subval = Value::eval(subexpr, subject, regexes)?;
                     match (op, subval) {
                         (expr::UnaryOperator::Not, Value::Bool(a)) => Ok(Value::Bool(!a)),
-                        (expr::UnaryOperator::Minus, Value::Int(a)) => Ok(Value::Int(-a)),
+                        (expr::UnaryOperator::Minus, Value::Int(a)) => a
+                            .checked_neg()
+                            .map(Value::Int)
+                            .ok_or_else(|| format!("Eval: arithmetic overflow in ‘-{}’", a)),
+                        (expr::UnaryOperator::Minus, Value::Decimal(a)) => a
+                            .checked_neg()
+                            .map(Value::Decimal)
+                            .ok_or_else(|| format!("Eval: arithmetic overflow in ‘-{}’", a)),
+                        (expr::UnaryOperator::Minus, Value::Null) => Ok(Value::Null),
                         (op, other) => Err(format!(
                             "Eval: unary operator {:?} does not apply to {:?}",
                             op, other
@@ -99,33 +504,135 @@ mod eval {
                     }
                 }
                 Expr::BinaryOp(op, subexpr_l, subexpr_r) => {
-                    let subval_l = Value::eval(subexpr_l, element)?;
-                    let subval_r = Value::eval(subexpr_r, element)?;
+                    let subval_l = Value::eval(subexpr_l, subject, regexes)?;
+                    let subval_r = Value::eval(subexpr_r, subject, regexes)?;
                     use expr::BinaryOperator::*;
                     use Value::*;
+                    // Mixed comparisons and arithmetic are done on decimals:
+                    let (subval_l, subval_r) = match (op, subval_l, subval_r) {
+                        (
+                            Equal | NotEqual | LessThan | LessEqual | GreaterThan | GreaterEqual
+                            | Plus | Minus | Multiply | Divide,
+                            l,
+                            r,
+                        ) if matches!((&l, &r), (Int(_), Decimal(_)) | (Decimal(_), Int(_))) => {
+                            (l.to_decimal(), r.to_decimal())
+                        }
+                        (_, l, r) => (l, r),
+                    };
                     match (op, subval_l, subval_r) {
+                        // Missing data is only equal to itself and never matches comparisons:
+                        (Equal, Null, r) => Ok(Bool(r == Null)),
+                        (Equal, l, Null) => Ok(Bool(l == Null)),
+                        (NotEqual, Null, r) => Ok(Bool(r != Null)),
+                        (NotEqual, l, Null) => Ok(Bool(l != Null)),
+                        (
+                            LessThan | LessEqual | GreaterThan | GreaterEqual,
+                            Null,
+                            Int(_) | Decimal(_),
+                        )
+                        | (
+                            LessThan | LessEqual | GreaterThan | GreaterEqual,
+                            Int(_) | Decimal(_),
+                            Null,
+                        ) => Ok(Bool(false)),
+                        (Plus | Minus | Multiply | Divide, Null, Int(_) | Decimal(_) | Null)
+                        | (Plus | Minus | Multiply | Divide, Int(_) | Decimal(_), Null) => Ok(Null),
+                        (Range | RangeInclusive, Null, Int(_) | Null)
+                        | (Range | RangeInclusive, Int(_), Null) => Ok(Null),
+                        (Matches | NotMatches, Null, Str(_)) => Ok(Bool(false)),
+                        (Matches | NotMatches, Str(l), Str(r)) => {
+                            let regex = regexes.get(&r).map_err(|err| format!("Eval: {}", err))?;
+                            Ok(Bool(regex.is_match(&l) == (*op == Matches)))
+                        }
                         (Or, Bool(l), Bool(r)) => Ok(Bool(l || r)),
                         (And, Bool(l), Bool(r)) => Ok(Bool(l && r)),
                         (Equal, Bool(l), Bool(r)) => Ok(Bool(l == r)),
                         (Equal, Int(l), Int(r)) => Ok(Bool(l == r)),
+                        (Equal, Decimal(l), Decimal(r)) => Ok(Bool(l == r)),
+                        (Equal, Str(l), Str(r)) => Ok(Bool(l == r)),
                         (Equal, Set(l), Set(r)) => Ok(Bool(l == r)),
                         (NotEqual, Bool(l), Bool(r)) => Ok(Bool(l != r)),
                         (NotEqual, Int(l), Int(r)) => Ok(Bool(l != r)),
+                        (NotEqual, Decimal(l), Decimal(r)) => Ok(Bool(l != r)),
+                        (NotEqual, Str(l), Str(r)) => Ok(Bool(l != r)),
                         (NotEqual, Set(l), Set(r)) => Ok(Bool(l != r)),
                         (LessThan, Int(l), Int(r)) => Ok(Bool(l < r)),
                         (LessEqual, Int(l), Int(r)) => Ok(Bool(l <= r)),
                         (GreaterThan, Int(l), Int(r)) => Ok(Bool(l > r)),
                         (GreaterEqual, Int(l), Int(r)) => Ok(Bool(l >= r)),
-                        (Plus, Int(l), Int(r)) => Ok(Int(l + r)),
-                        (Minus, Int(l), Int(r)) => Ok(Int(l - r)),
-                        (Multiply, Int(l), Int(r)) => Ok(Int(l * r)),
-                        (Divide, Int(l), Int(r)) => Ok(Int(l / r)),
+                        (LessThan, Decimal(l), Decimal(r)) => Ok(Bool(l < r)),
+                        (LessEqual, Decimal(l), Decimal(r)) => Ok(Bool(l <= r)),
+                        (GreaterThan, Decimal(l), Decimal(r)) => Ok(Bool(l > r)),
+                        (GreaterEqual, Decimal(l), Decimal(r)) => Ok(Bool(l >= r)),
+                        (Plus, Int(l), Int(r)) => {
+                            l.checked_add(r).map(Int).ok_or_else(|| overflow("+", &l, &r))
+                        }
+                        (Minus, Int(l), Int(r)) => {
+                            l.checked_sub(r).map(Int).ok_or_else(|| overflow("-", &l, &r))
+                        }
+                        (Multiply, Int(l), Int(r)) => {
+                            l.checked_mul(r).map(Int).ok_or_else(|| overflow("*", &l, &r))
+                        }
+                        (Divide, Int(l), Int(r)) => {
+                            l.checked_div(r).map(Int).ok_or_else(|| overflow("/", &l, &r))
+                        }
+                        (Plus, Decimal(l), Decimal(r)) => {
+                            l.checked_add(r).map(Decimal).ok_or_else(|| overflow("+", &l, &r))
+                        }
+                        (Minus, Decimal(l), Decimal(r)) => {
+                            l.checked_sub(r).map(Decimal).ok_or_else(|| overflow("-", &l, &r))
+                        }
+                        (Multiply, Decimal(l), Decimal(r)) => {
+                            l.checked_mul(r).map(Decimal).ok_or_else(|| overflow("*", &l, &r))
+                        }
+                        (Divide, Decimal(l), Decimal(r)) => {
+                            l.checked_div(r).map(Decimal).ok_or_else(|| overflow("/", &l, &r))
+                        }
+                        (Plus, Str(l), Str(r)) => Ok(Str(l + &r)),
                         (Plus, Set(l), Set(r)) => Ok(Set(l.union(&r).cloned().collect())),
                         (Minus, Set(l), Set(r)) => Ok(Set(l.difference(&r).cloned().collect())),
-                        (InSet, l @ Int(_), Set(r)) => Ok(Bool(r.contains(&l))),
-                        (InSet, l @ Bool(_), Set(r)) => Ok(Bool(r.contains(&l))),
-                        // Here’s a little inconsequency, because we allow sets of sets… But well.
-                        (InSet, Set(l), Set(r)) => Ok(Bool(r.is_superset(&l))),
+                        (Intersection, Set(l), Set(r)) => {
+                            Ok(Set(l.intersection(&r).cloned().collect()))
+                        }
+                        (SymmetricDifference, Set(l), Set(r)) => {
+                            Ok(Set(l.symmetric_difference(&r).cloned().collect()))
+                        }
+                        (InSet | NotInSet, Decimal(l), _) => Err(format!(
+                            "Eval: decimal numbers cannot be used with ‘in’, but got {}; use comparisons instead",
+                            l
+                        )),
+                        // Before `in` only meant membership, it also meant `subset` for sets:
+                        (InSet | NotInSet, Set(l), Set(r))
+                            if !r.iter().any(|x| matches!(x, Set(_))) =>
+                        {
+                            Err(format!(
+                                "Eval: ‘in’ tests membership, and {:?} cannot be in a set without sets; use ‘subset’ instead",
+                                l
+                            ))
+                        }
+                        (InSet, l @ (Int(_) | Bool(_) | Str(_) | Set(_)), Set(r)) => {
+                            Ok(Bool(r.contains(&l)))
+                        }
+                        (NotInSet, l @ (Int(_) | Bool(_) | Str(_) | Set(_)), Set(r)) => {
+                            Ok(Bool(!r.contains(&l)))
+                        }
+                        (Range | RangeInclusive, Int(l), Int(r)) => {
+                            let end = if *op == RangeInclusive { r as i64 + 1 } else { r as i64 };
+                            if end - l as i64 > MAX_RANGE_LEN {
+                                return Err(format!(
+                                    "Eval: ranges can have at most {} numbers, but got ‘{}{}{}’",
+                                    MAX_RANGE_LEN,
+                                    l,
+                                    if *op == RangeInclusive { "..=" } else { ".." },
+                                    r
+                                ));
+                            }
+                            Ok(Set((l as i64..end).map(|a| Int(a as i32)).collect()))
+                        }
+                        (Subset, Set(l), Set(r)) => Ok(Bool(l.is_subset(&r))),
+                        (ProperSubset, Set(l), Set(r)) => Ok(Bool(l.is_subset(&r) && l != r)),
+                        (Superset, Set(l), Set(r)) => Ok(Bool(l.is_superset(&r))),
                         (op, other_l, other_r) => Err(format!(
                             "Eval: binary operator {:?} does not apply to ({:?}, {:?})",
                             op, other_l, other_r
@@ -134,6 +641,269 @@ mod eval {
                 }
             }
         }
+
+        fn to_decimal(&self) -> Self {
+            match self {
+                Value::Int(a) => Value::Decimal(Decimal::from(*a)),
+                other => other.clone(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::sync::Arc;
+
+        impl Subject for HashMap<&str, Value> {
+            fn lookup(&self, symbol: &str) -> Result<Option<Value>, String> {
+                Ok(self.get(symbol).cloned())
+            }
+        }
+
+        fn eval(source: &str, subject: &HashMap<&str, Value>) -> Result<Value, String> {
+            let query = super::super::Query::new(source)?;
+            Value::eval(&query.compiled, subject, &query.regexes)
+        }
+
+        fn set(items: &[i32]) -> Value {
+            Value::Set(items.iter().map(|&a| Value::Int(a)).collect())
+        }
+
+        #[test]
+        fn test_eval_matches() {
+            let query = super::super::Query::new(
+                r#"name ~ "ium$" && name ~ "ium$" && name !~ "^" + symbol + "[a-z]""#,
+            )
+            .unwrap();
+            let compiled = || query.regexes.0.lock().unwrap().len();
+            // Literal patterns are compiled once, right away:
+            assert_eq!(compiled(), 1);
+            let elements = [("Lithium", "Li"), ("Helium", "He"), ("Sodium", "Na")];
+            for _ in 0..3 {
+                for (name, symbol) in elements.iter() {
+                    let subject = HashMap::from([
+                        ("name", Value::Str(name.to_string())),
+                        ("symbol", Value::Str(symbol.to_string())),
+                    ]);
+                    assert_eq!(query.evaluate_on(&subject), Ok(*symbol == "Na"));
+                }
+            }
+            // Others once per distinct pattern, also for clones of the query:
+            assert_eq!(compiled(), 1 + elements.len());
+            let clone = query.clone();
+            assert!(Arc::ptr_eq(&query.regexes, &clone.regexes));
+
+            let subject = HashMap::from([("name", Value::Null), ("symbol", Value::Null)]);
+            assert_eq!(eval(r#"name ~ "a""#, &subject), Ok(Value::Bool(false)));
+            assert_eq!(eval(r#"name !~ "a""#, &subject), Ok(Value::Bool(false)));
+            assert!(eval(r#""a" ~ "(" + "a""#, &subject)
+                .unwrap_err()
+                .contains("invalid regular expression"));
+            assert!(super::super::Query::new(r#"name ~ "(""#)
+                .unwrap_err()
+                .starts_with("Error parsing Query: invalid regular expression"));
+        }
+
+        #[test]
+        fn test_eval_set_operators() {
+            let subject = HashMap::from([("a", set(&[1, 2, 3]))]);
+            assert_eq!(eval("2 in a", &subject), Ok(Value::Bool(true)));
+            assert_eq!(eval("2 not in a", &subject), Ok(Value::Bool(false)));
+            assert_eq!(eval("{1, 2} subset a", &subject), Ok(Value::Bool(true)));
+            assert_eq!(eval("a proper_subset a", &subject), Ok(Value::Bool(false)));
+            assert_eq!(eval("a superset {}", &subject), Ok(Value::Bool(true)));
+            assert_eq!(eval("(a & {2, 4}) ^ {3}", &subject), Ok(set(&[2, 3])));
+            assert_eq!(
+                eval("{1, 2} in a", &subject),
+                Err("Eval: ‘in’ tests membership, and {Int(1), Int(2)} cannot be in a set without sets; use ‘subset’ instead".to_string())
+            );
+            assert!(eval("{} not in a", &subject)
+                .unwrap_err()
+                .contains("use ‘subset’ instead"));
+            // Sets of sets:
+            assert_eq!(
+                eval("{1, 2} in {a, {1, 2}}", &subject),
+                Ok(Value::Bool(true))
+            );
+            assert_eq!(eval("{1} in {a, {1, 2}}", &subject), Ok(Value::Bool(false)));
+            assert_eq!(eval("{1} not in {a, {}}", &subject), Ok(Value::Bool(true)));
+            assert_eq!(eval("a not in {a}", &subject), Ok(Value::Bool(false)));
+        }
+
+        #[test]
+        fn test_eval_range() {
+            let subject = HashMap::new();
+            assert_eq!(eval("1..4", &subject), Ok(set(&[1, 2, 3])));
+            assert_eq!(eval("-1..=1", &subject), Ok(set(&[-1, 0, 1])));
+            assert_eq!(eval("3..1", &subject), Ok(set(&[])));
+            assert_eq!(eval("null..=1", &subject), Ok(Value::Null));
+            assert!(eval("0..=99999", &subject).is_ok());
+            assert_eq!(
+                eval("0..=100000", &subject),
+                Err(
+                    "Eval: ranges can have at most 100000 numbers, but got ‘0..=100000’"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn test_eval_set_comprehension() {
+            let subject = HashMap::from([("group", Value::Int(8)), ("a", set(&[-2, 1, 2, 3]))]);
+            assert_eq!(eval("{x * 2 | x in a}", &subject), Ok(set(&[-4, 2, 4, 6])));
+            assert_eq!(
+                eval("{x | x in a, x / 2 * 2 == x}", &subject),
+                Ok(set(&[-2, 2]))
+            );
+            // The variable shadows the field, which is still there outside:
+            assert_eq!(
+                eval("{group | group in a} + {group}", &subject),
+                Ok(set(&[-2, 1, 2, 3, 8]))
+            );
+            assert_eq!(eval("{x + group | x in 0..2}", &subject), Ok(set(&[8, 9])));
+            assert_eq!(eval("{x | x in null}", &subject), Ok(Value::Null));
+            assert!(eval("{x / 2.5 | x in a}", &subject)
+                .unwrap_err()
+                .contains("decimal numbers cannot be put in sets"));
+            assert!(eval("{x | x in a, x}", &subject)
+                .unwrap_err()
+                .contains("filters must be booleans"));
+            assert!(eval("{x | x in group}", &subject)
+                .unwrap_err()
+                .contains("need a set after ‘in’"));
+        }
+    }
+}
+
+mod decimal {
+    /// Fixed-point number with 6 decimal places, so that it can be compared and put in sets.
+    #[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+    pub struct Decimal(i128);
+
+    const SCALE: i128 = 1_000_000;
+
+    impl From<i32> for Decimal {
+        fn from(a: i32) -> Self {
+            Self(a as i128 * SCALE)
+        }
+    }
+
+    impl From<f64> for Decimal {
+        fn from(a: f64) -> Self {
+            Self((a * SCALE as f64).round() as i128)
+        }
+    }
+
+    impl std::str::FromStr for Decimal {
+        type Err = String;
+
+        /// Exact, unlike going through `f64`, e.g. `2.55` or `-0.5`.
+        fn from_str(input: &str) -> Result<Self, Self::Err> {
+            let error = || format!("invalid decimal number: ‘{}’", input);
+            let (negative, unsigned) = match input.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, input),
+            };
+            let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+            if int.is_empty() || !(int.chars().chain(frac.chars())).all(|c| c.is_ascii_digit()) {
+                return Err(error());
+            }
+            if frac.len() > 6 {
+                return Err(format!(
+                    "decimal numbers can have at most 6 decimal places: ‘{}’",
+                    input
+                ));
+            }
+            let int = int.parse::<i128>().map_err(|_| error())?;
+            let frac = format!("{:0<6}", frac).parse::<i128>().unwrap();
+            let abs = int
+                .checked_mul(SCALE)
+                .and_then(|a| a.checked_add(frac))
+                .ok_or_else(error)?;
+            Ok(Self(if negative { -abs } else { abs }))
+        }
+    }
+
+    impl Decimal {
+        pub fn checked_add(self, other: Self) -> Option<Self> {
+            self.0.checked_add(other.0).map(Self)
+        }
+
+        pub fn checked_sub(self, other: Self) -> Option<Self> {
+            self.0.checked_sub(other.0).map(Self)
+        }
+
+        /// Truncates to 6 decimal places.
+        pub fn checked_mul(self, other: Self) -> Option<Self> {
+            self.0.checked_mul(other.0).map(|a| Self(a / SCALE))
+        }
+
+        /// Truncates to 6 decimal places, `None` also when dividing by zero.
+        pub fn checked_div(self, other: Self) -> Option<Self> {
+            self.0.checked_mul(SCALE)?.checked_div(other.0).map(Self)
+        }
+
+        pub fn checked_neg(self) -> Option<Self> {
+            self.0.checked_neg().map(Self)
+        }
+    }
+
+    impl From<Decimal> for f64 {
+        fn from(a: Decimal) -> Self {
+            a.0 as f64 / SCALE as f64
+        }
+    }
+
+    impl std::fmt::Display for Decimal {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let sign = if self.0 < 0 { "-" } else { "" };
+            let (int, frac) = (self.0.abs() / SCALE, self.0.abs() % SCALE);
+            if frac == 0 {
+                write!(f, "{}{}", sign, int)
+            } else {
+                let frac = format!("{:06}", frac);
+                write!(f, "{}{}.{}", sign, int, frac.trim_end_matches('0'))
+            }
+        }
+    }
+
+    impl std::fmt::Debug for Decimal {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_decimal_display() {
+            assert_eq!(Decimal::from(55.845).to_string(), "55.845");
+            assert_eq!(Decimal::from(-0.5).to_string(), "-0.5");
+            assert_eq!(Decimal::from(98).to_string(), "98");
+            assert!(Decimal::from(98) < Decimal::from(98.000001));
+        }
+
+        #[test]
+        fn test_decimal_from_str() {
+            assert_eq!("2.55".parse(), Ok(Decimal::from(2.55)));
+            assert_eq!("-0.5".parse(), Ok(Decimal::from(-0.5)));
+            assert_eq!("7".parse(), Ok(Decimal::from(7)));
+            assert!("1.0000001".parse::<Decimal>().is_err());
+            assert!(".5".parse::<Decimal>().is_err());
+        }
+
+        #[test]
+        fn test_decimal_arithmetic() {
+            let (a, b) = (Decimal::from(2.5), Decimal::from(-0.4));
+            assert_eq!(a.checked_add(b), Some(Decimal::from(2.1)));
+            assert_eq!(a.checked_sub(b), Some(Decimal::from(2.9)));
+            assert_eq!(a.checked_mul(b), Some(Decimal::from(-1)));
+            assert_eq!(a.checked_div(b), Some(Decimal::from(-6.25)));
+            assert_eq!(a.checked_div(Decimal::from(0)), None);
+        }
     }
 }
 
@@ -141,11 +911,19 @@ mod expr {
     #[derive(PartialEq, Eq, Debug, Clone)]
     pub enum Expr {
         // Literals:
+        LNull,
         LBool(bool),
         LInt(i32),
+        LDecimal(Decimal),
+        LStr(String),
         LSet(Vec<Expr>),
+        // Set comprehensions, e.g. "{x * 2 | x in oxidation_states.common, x > 0}", with the
+        // element, the variable, the set and the optional filter:
+        LSetComprehension(Box<Expr>, String, Box<Expr>, Option<Box<Expr>>),
         // Symbols, e.g. "oxidation_states.notable":
         Symbol(String),
+        // Function calls, e.g. "len(oxidation_states.common)":
+        Call(String, Vec<Expr>),
         // Operators:
         BinaryOp(BinaryOperator, Box<Expr>, Box<Expr>),
         UnaryOp(UnaryOperator, Box<Expr>),
@@ -159,12 +937,24 @@ mod expr {
         // Comparison:
         Equal,
         NotEqual,
+        /// Regular expression, e.g. `name ~ "ium$"`.
+        Matches,
+        NotMatches,
         LessThan,
         LessEqual,
         GreaterThan,
         GreaterEqual,
         // Set:
         InSet,
+        NotInSet,
+        Subset,
+        Superset,
+        ProperSubset,
+        Intersection,
+        SymmetricDifference,
+        /// Integers, e.g. `11..19` without 19, and `-4..=8` with 8.
+        Range,
+        RangeInclusive,
         // Arithmetic:
         Plus,
         Minus,
@@ -178,85 +968,151 @@ mod expr {
         Minus,
     }
 
+    use super::decimal::Decimal;
     use nom::{
         branch::alt,
-        bytes::complete::tag,
-        character::complete::{alpha1, alphanumeric1, multispace0},
-        combinator::{map, recognize, value},
+        bytes::complete::{escaped_transform, tag},
+        character::complete::{alpha1, alphanumeric1, digit1, multispace0, multispace1, none_of},
+        combinator::{map, map_res, not, opt, recognize, value},
         error::VerboseError,
-        multi::{fold_many0, many0_count, separated_list0},
-        sequence::{delimited, pair, preceded},
+        multi::{fold_many0, many0, many0_count, separated_list0},
+        sequence::{delimited, pair, preceded, tuple},
         IResult,
     };
 
     type IR<'a, A> = IResult<&'a str, A, VerboseError<&'a str>>;
 
     impl Expr {
-        pub fn parse(input: &str) -> IR<Self> {
+        pub fn parse(input: &str) -> IR<'_, Self> {
             Self::or(input)
         }
 
-        fn or(input: &str) -> IR<Self> {
+        /// Visits this expression and all its subexpressions, stopping at the first error.
+        pub fn walk(&self, f: &mut dyn FnMut(&Self) -> Result<(), String>) -> Result<(), String> {
+            f(self)?;
+            match self {
+                Self::LSet(subexprs) | Self::Call(_, subexprs) => {
+                    subexprs.iter().try_for_each(|sx| sx.walk(f))
+                }
+                Self::BinaryOp(_, l, r) => {
+                    l.walk(f)?;
+                    r.walk(f)
+                }
+                Self::LSetComprehension(element, _, set, filter) => {
+                    element.walk(f)?;
+                    set.walk(f)?;
+                    filter.as_ref().map_or(Ok(()), |filter| filter.walk(f))
+                }
+                Self::UnaryOp(_, subexpr) => subexpr.walk(f),
+                _ => Ok(()),
+            }
+        }
+
+        fn or(input: &str) -> IR<'_, Self> {
             Self::binary_op("||", BinaryOperator::Or, Self::and)(input)
         }
 
-        fn and(input: &str) -> IR<Self> {
+        fn and(input: &str) -> IR<'_, Self> {
             Self::binary_op("&&", BinaryOperator::And, Self::equal)(input)
         }
 
-        fn equal(input: &str) -> IR<Self> {
+        fn equal(input: &str) -> IR<'_, Self> {
             Self::binary_op("==", BinaryOperator::Equal, Self::not_equal)(input)
         }
 
-        fn not_equal(input: &str) -> IR<Self> {
-            Self::binary_op("!=", BinaryOperator::NotEqual, Self::less_than)(input)
+        fn not_equal(input: &str) -> IR<'_, Self> {
+            Self::binary_op("!=", BinaryOperator::NotEqual, Self::matches)(input)
+        }
+
+        fn matches(input: &str) -> IR<'_, Self> {
+            Self::binary_op("~", BinaryOperator::Matches, Self::not_matches)(input)
+        }
+
+        fn not_matches(input: &str) -> IR<'_, Self> {
+            Self::binary_op("!~", BinaryOperator::NotMatches, Self::less_than)(input)
         }
 
-        fn less_than(input: &str) -> IR<Self> {
+        fn less_than(input: &str) -> IR<'_, Self> {
             Self::binary_op("<", BinaryOperator::LessThan, Self::less_equal)(input)
         }
 
-        fn less_equal(input: &str) -> IR<Self> {
+        fn less_equal(input: &str) -> IR<'_, Self> {
             Self::binary_op("<=", BinaryOperator::LessEqual, Self::greater_than)(input)
         }
 
-        fn greater_than(input: &str) -> IR<Self> {
+        fn greater_than(input: &str) -> IR<'_, Self> {
             Self::binary_op(">", BinaryOperator::GreaterThan, Self::greater_equal)(input)
         }
 
-        fn greater_equal(input: &str) -> IR<Self> {
+        fn greater_equal(input: &str) -> IR<'_, Self> {
             Self::binary_op(">=", BinaryOperator::GreaterEqual, Self::in_set)(input)
         }
 
-        fn in_set(input: &str) -> IR<Self> {
-            Self::binary_op("in", BinaryOperator::InSet, Self::plus)(input)
+        fn in_set(input: &str) -> IR<'_, Self> {
+            Self::binary_op("in", BinaryOperator::InSet, Self::not_in_set)(input)
+        }
+
+        fn not_in_set(input: &str) -> IR<'_, Self> {
+            Self::binary_op("not in", BinaryOperator::NotInSet, Self::subset)(input)
+        }
+
+        fn subset(input: &str) -> IR<'_, Self> {
+            Self::binary_op("subset", BinaryOperator::Subset, Self::superset)(input)
+        }
+
+        fn superset(input: &str) -> IR<'_, Self> {
+            Self::binary_op("superset", BinaryOperator::Superset, Self::proper_subset)(input)
+        }
+
+        fn proper_subset(input: &str) -> IR<'_, Self> {
+            Self::binary_op(
+                "proper_subset",
+                BinaryOperator::ProperSubset,
+                Self::symmetric_difference,
+            )(input)
+        }
+
+        fn symmetric_difference(input: &str) -> IR<'_, Self> {
+            Self::binary_op("^", BinaryOperator::SymmetricDifference, Self::intersection)(input)
+        }
+
+        fn intersection(input: &str) -> IR<'_, Self> {
+            Self::binary_op("&", BinaryOperator::Intersection, Self::range_inclusive)(input)
+        }
+
+        fn range_inclusive(input: &str) -> IR<'_, Self> {
+            Self::binary_op("..=", BinaryOperator::RangeInclusive, Self::range)(input)
+        }
+
+        fn range(input: &str) -> IR<'_, Self> {
+            Self::binary_op("..", BinaryOperator::Range, Self::plus)(input)
         }
 
-        fn plus(input: &str) -> IR<Self> {
+        fn plus(input: &str) -> IR<'_, Self> {
             Self::binary_op("+", BinaryOperator::Plus, Self::minus)(input)
         }
 
-        fn minus(input: &str) -> IR<Self> {
+        fn minus(input: &str) -> IR<'_, Self> {
             Self::binary_op("-", BinaryOperator::Minus, Self::multiply)(input)
         }
 
-        fn multiply(input: &str) -> IR<Self> {
+        fn multiply(input: &str) -> IR<'_, Self> {
             Self::binary_op("*", BinaryOperator::Multiply, Self::divide)(input)
         }
 
-        fn divide(input: &str) -> IR<Self> {
+        fn divide(input: &str) -> IR<'_, Self> {
             Self::binary_op("/", BinaryOperator::Divide, Self::not)(input)
         }
 
-        fn not(input: &str) -> IR<Self> {
+        fn not(input: &str) -> IR<'_, Self> {
             Self::unary_op("!", UnaryOperator::Not, Self::unary_minus)(input)
         }
 
-        fn unary_minus(input: &str) -> IR<Self> {
+        fn unary_minus(input: &str) -> IR<'_, Self> {
             Self::unary_op("-", UnaryOperator::Minus, Self::parens)(input)
         }
 
-        fn parens(input: &str) -> IR<Self> {
+        fn parens(input: &str) -> IR<'_, Self> {
             alt((
                 delimited(
                     preceded(multispace0, tag("(")),
@@ -264,71 +1120,182 @@ mod expr {
                     preceded(multispace0, tag(")")),
                 ),
                 Self::literal_set,
+                Self::literal_null,
                 Self::literal_bool,
+                Self::literal_decimal,
                 Self::literal_int,
+                Self::literal_str,
+                Self::call,
                 Self::symbol,
             ))(input)
         }
 
-        fn literal_bool(input: &str) -> IR<Self> {
+        fn literal_null(input: &str) -> IR<'_, Self> {
+            value(Self::LNull, preceded(multispace0, tag("null")))(input)
+        }
+
+        fn literal_bool(input: &str) -> IR<'_, Self> {
             alt((
                 value(Self::LBool(true), preceded(multispace0, tag("true"))),
                 value(Self::LBool(false), preceded(multispace0, tag("false"))),
             ))(input)
         }
 
-        fn literal_int(input: &str) -> IR<Self> {
+        fn literal_int(input: &str) -> IR<'_, Self> {
             map(preceded(multispace0, nom::character::complete::i32), |i| {
                 Self::LInt(i)
             })(input)
         }
 
-        fn symbol(input: &str) -> IR<Self> {
-            fn part(input: &str) -> IR<&str> {
-                recognize(pair(alpha1, many0_count(alt((alphanumeric1, tag("_"))))))(input)
-            }
+        /// Needs digits on both sides of the dot, e.g. `2.55`.
+        fn literal_decimal(input: &str) -> IR<'_, Self> {
+            map_res(
+                preceded(
+                    multispace0,
+                    recognize(pair(pair(opt(tag("-")), digit1), pair(tag("."), digit1))),
+                ),
+                |s: &str| s.parse::<Decimal>().map(Self::LDecimal),
+            )(input)
+        }
+
+        fn literal_str(input: &str) -> IR<'_, Self> {
+            map(
+                preceded(
+                    multispace0,
+                    delimited(
+                        tag("\""),
+                        opt(escaped_transform(
+                            none_of("\\\""),
+                            '\\',
+                            alt((value("\\", tag("\\")), value("\"", tag("\"")))),
+                        )),
+                        tag("\""),
+                    ),
+                ),
+                |s| Self::LStr(s.unwrap_or_default()),
+            )(input)
+        }
+
+        fn identifier(input: &str) -> IR<'_, &str> {
+            recognize(pair(alpha1, many0_count(alt((alphanumeric1, tag("_"))))))(input)
+        }
+
+        fn symbol(input: &str) -> IR<'_, Self> {
             map(
                 preceded(
                     multispace0,
-                    recognize(pair(part, many0_count(pair(tag("."), part)))),
+                    recognize(pair(
+                        Self::identifier,
+                        many0_count(pair(tag("."), Self::identifier)),
+                    )),
                 ),
                 |s| Self::Symbol(s.to_string()),
             )(input)
         }
 
-        fn literal_set(input: &str) -> IR<Self> {
-            delimited(
-                preceded(multispace0, tag("{")),
-                map(separated_list0(tag(","), Self::parse), |xs| Self::LSet(xs)),
-                preceded(multispace0, tag("}")),
+        fn call(input: &str) -> IR<'_, Self> {
+            map(
+                pair(
+                    preceded(multispace0, Self::identifier),
+                    delimited(
+                        tag("("),
+                        separated_list0(preceded(multispace0, tag(",")), Self::parse),
+                        preceded(multispace0, tag(")")),
+                    ),
+                ),
+                |(name, args)| Self::Call(name.to_string(), args),
             )(input)
         }
 
+        /// Also set comprehensions, e.g. `{x * 2 | x in a}` or, with a filter after the comma,
+        /// `{x | x in a, x > 0}`. The first expression is parsed once for both, so that nested
+        /// sets don’t take exponential time.
+        fn literal_set(input: &str) -> IR<'_, Self> {
+            let (input, first) =
+                preceded(preceded(multispace0, tag("{")), opt(Self::parse))(input)?;
+            let (input, expr) = match first {
+                None => (input, Self::LSet(vec![])),
+                Some(first) => match Self::set_comprehension_rest(input) {
+                    Ok((input, (variable, set, filter))) => (
+                        input,
+                        Self::LSetComprehension(
+                            Box::new(first),
+                            variable.to_string(),
+                            Box::new(set),
+                            filter.map(Box::new),
+                        ),
+                    ),
+                    Err(nom::Err::Error(_)) => {
+                        let (input, mut subexprs) = many0(preceded(tag(","), Self::parse))(input)?;
+                        subexprs.insert(0, first);
+                        (input, Self::LSet(subexprs))
+                    }
+                    Err(err) => return Err(err),
+                },
+            };
+            let (input, _) = preceded(multispace0, tag("}"))(input)?;
+            Ok((input, expr))
+        }
+
+        /// Everything after the element of a set comprehension: `| x in a, x > 0`.
+        fn set_comprehension_rest(input: &str) -> IR<'_, (&str, Self, Option<Self>)> {
+            tuple((
+                preceded(
+                    preceded(multispace0, tag("|")),
+                    preceded(multispace0, Self::identifier),
+                ),
+                preceded(preceded(multispace0, Self::operator("in")), Self::parse),
+                opt(preceded(preceded(multispace0, tag(",")), Self::parse)),
+            ))(input)
+        }
+
         // ---------------------- operator helpers ---------------------- //
 
         fn binary_op<'a, F>(
             op_tag: &'a str,
             op_variant: BinaryOperator,
             mut lower_precedence: F,
-        ) -> impl FnMut(&'a str) -> IR<Self>
+        ) -> impl FnMut(&'a str) -> IR<'a, Self>
         where
             F: FnMut(&'a str) -> IR<Self> + Copy,
         {
             move |input| {
                 let (input, init) = lower_precedence(input)?;
                 fold_many0(
-                    preceded(preceded(multispace0, tag(op_tag)), lower_precedence),
+                    preceded(
+                        preceded(multispace0, Self::operator(op_tag)),
+                        lower_precedence,
+                    ),
                     move || init.clone(),
                     |acc, item| Self::BinaryOp(op_variant, Box::new(acc), Box::new(item)),
                 )(input)
             }
         }
 
+        /// Word operators, like `not in`, can have any whitespace between the words, but cannot
+        /// be directly followed by a symbol character, so that e.g. `in` isn’t found in `inert`.
+        fn operator<'a>(op_tag: &'a str) -> impl FnMut(&'a str) -> IR<'a, &'a str> {
+            move |input| {
+                if !op_tag.starts_with(char::is_alphabetic) {
+                    return tag(op_tag)(input);
+                }
+                let mut rest = input;
+                for (i, word) in op_tag.split(' ').enumerate() {
+                    if i > 0 {
+                        rest = multispace1(rest)?.0;
+                    }
+                    rest = tag(word)(rest)?.0;
+                }
+                not(alt((alphanumeric1, tag("_"))))(rest)?;
+                Ok((rest, &input[..input.len() - rest.len()]))
+            }
+        }
+
         fn unary_op<'a, F>(
             op_tag: &'a str,
             op_variant: UnaryOperator,
             lower_precedence: F,
-        ) -> impl FnMut(&'a str) -> IR<Self>
+        ) -> impl FnMut(&'a str) -> IR<'a, Self>
         where
             F: FnMut(&'a str) -> IR<Self> + Copy,
         {
@@ -351,6 +1318,7 @@ mod expr {
         use super::*;
 
         #[test]
+        #[allow(clippy::redundant_pattern_matching)]
         fn test_expr_symbol() {
             assert_eq!(
                 Expr::symbol("atomic_number "),
@@ -383,6 +1351,202 @@ mod expr {
         }
 
         #[test]
+        fn test_expr_literal_decimal() {
+            assert_eq!(
+                Expr::literal_decimal("  -2.55 "),
+                Ok((" ", Expr::LDecimal(Decimal::from(-2.55))))
+            );
+            assert!(Expr::literal_decimal("2 ").is_err());
+            assert!(Expr::literal_decimal("2.").is_err());
+            assert_eq!(
+                Expr::parse("electronegativity > 3.0"),
+                Expr::parse("electronegativity > (3.0)"),
+            );
+        }
+
+        #[test]
+        fn test_expr_literal_str() {
+            assert_eq!(
+                Expr::literal_str(r#"  "Iron" "#),
+                Ok((" ", Expr::LStr("Iron".to_string())))
+            );
+            assert_eq!(
+                Expr::literal_str(r#""" "#),
+                Ok((" ", Expr::LStr(String::new())))
+            );
+            assert_eq!(
+                Expr::literal_str(r#""a \"b\" \\ c""#),
+                Ok(("", Expr::LStr(r#"a "b" \ c"#.to_string())))
+            );
+            assert!(Expr::literal_str(r#""unterminated"#).is_err());
+        }
+
+        #[test]
+        fn test_expr_set_of_str() {
+            use Expr::*;
+            assert_eq!(
+                Expr::parse(r#"symbol in {"Fe", "Co"}"#),
+                Ok((
+                    "",
+                    BinaryOp(
+                        BinaryOperator::InSet,
+                        Box::new(Symbol("symbol".to_string())),
+                        Box::new(LSet(vec![LStr("Fe".to_string()), LStr("Co".to_string())])),
+                    )
+                ))
+            );
+        }
+
+        #[test]
+        fn test_expr_matches() {
+            use Expr::*;
+            assert_eq!(
+                Expr::parse(r#"name !~ "ium$" == true"#),
+                Ok((
+                    "",
+                    BinaryOp(
+                        BinaryOperator::Equal,
+                        Box::new(BinaryOp(
+                            BinaryOperator::NotMatches,
+                            Box::new(Symbol("name".to_string())),
+                            Box::new(LStr("ium$".to_string())),
+                        )),
+                        Box::new(LBool(true)),
+                    )
+                ))
+            );
+            assert_eq!(
+                Expr::parse(r#"name ~ "^" + symbol"#),
+                Expr::parse(r#"name ~ ("^" + symbol)"#),
+            );
+        }
+
+        #[test]
+        fn test_expr_call() {
+            use Expr::*;
+            assert_eq!(
+                Expr::parse("max(oxidation_states.common) == group - 10"),
+                Ok((
+                    "",
+                    BinaryOp(
+                        BinaryOperator::Equal,
+                        Box::new(Call(
+                            "max".to_string(),
+                            vec![Symbol("oxidation_states.common".to_string())]
+                        )),
+                        Box::new(BinaryOp(
+                            BinaryOperator::Minus,
+                            Box::new(Symbol("group".to_string())),
+                            Box::new(LInt(10))
+                        )),
+                    )
+                ))
+            );
+            assert_eq!(
+                Expr::parse("f( 1 , {2})"),
+                Ok((
+                    "",
+                    Call("f".to_string(), vec![LInt(1), LSet(vec![LInt(2)])])
+                ))
+            );
+            assert_eq!(Expr::parse("f()"), Ok(("", Call("f".to_string(), vec![]))));
+            // Only without a space before the parenthesis:
+            assert_eq!(
+                Expr::parse("len (a)"),
+                Ok((" (a)", Symbol("len".to_string())))
+            );
+        }
+
+        #[test]
+        fn test_expr_set_operators() {
+            use BinaryOperator::*;
+            use Expr::*;
+            let symbol = |s: &str| Box::new(Symbol(s.to_string()));
+            assert_eq!(
+                Expr::parse("z not  in a & b ^ c"),
+                Ok((
+                    "",
+                    BinaryOp(
+                        NotInSet,
+                        symbol("z"),
+                        Box::new(BinaryOp(
+                            SymmetricDifference,
+                            Box::new(BinaryOp(Intersection, symbol("a"), symbol("b"))),
+                            symbol("c"),
+                        )),
+                    )
+                ))
+            );
+            assert_eq!(Expr::parse("a & b && c"), Expr::parse("(a & b) && c"),);
+            assert_eq!(
+                Expr::parse("a proper_subset b"),
+                Ok(("", BinaryOp(ProperSubset, symbol("a"), symbol("b"))))
+            );
+            // Not an operator, but the beginning of a symbol:
+            assert_eq!(
+                Expr::parse("a inert"),
+                Ok((" inert", Symbol("a".to_string())))
+            );
+        }
+
+        #[test]
+        fn test_expr_range_and_comprehension() {
+            use BinaryOperator::*;
+            use Expr::*;
+            let symbol = |s: &str| Box::new(Symbol(s.to_string()));
+            assert_eq!(
+                Expr::parse("z in 11..19"),
+                Ok((
+                    "",
+                    BinaryOp(
+                        InSet,
+                        symbol("z"),
+                        Box::new(BinaryOp(Range, Box::new(LInt(11)), Box::new(LInt(19)))),
+                    )
+                ))
+            );
+            assert_eq!(
+                Expr::parse("-4..=group + 1"),
+                Ok((
+                    "",
+                    BinaryOp(
+                        RangeInclusive,
+                        Box::new(UnaryOp(UnaryOperator::Minus, Box::new(LInt(4)))),
+                        Box::new(BinaryOp(Plus, symbol("group"), Box::new(LInt(1)))),
+                    )
+                ))
+            );
+            assert_eq!(
+                Expr::parse("{x * 2 | x in a, x > 0}"),
+                Ok((
+                    "",
+                    LSetComprehension(
+                        Box::new(BinaryOp(Multiply, symbol("x"), Box::new(LInt(2)))),
+                        "x".to_string(),
+                        symbol("a"),
+                        Some(Box::new(BinaryOp(
+                            GreaterThan,
+                            symbol("x"),
+                            Box::new(LInt(0))
+                        ))),
+                    )
+                ))
+            );
+            // Still a set literal:
+            assert_eq!(
+                Expr::parse("{a || b, c}"),
+                Ok((
+                    "",
+                    LSet(vec![
+                        BinaryOp(Or, symbol("a"), symbol("b")),
+                        Symbol("c".to_string())
+                    ])
+                ))
+            );
+        }
+
+        #[test]
+        #[allow(unused_imports)]
         fn test_expr_binary_op() {
             use BinaryOperator::*;
             use Expr::*;
@@ -401,6 +1565,7 @@ mod expr {
         }
 
         #[test]
+        #[allow(unused_imports)]
         fn test_expr_precedence() {
             use BinaryOperator::*;
             use Expr::*;
@@ -436,6 +1601,7 @@ mod expr {
         }
 
         #[test]
+        #[allow(unused_imports)]
         fn test_expr_unary_not() {
             use BinaryOperator::*;
             use Expr::*;